@@ -1,10 +1,15 @@
 use clap::Parser;
 use hdrhistogram::Histogram;
 use std::error::Error;
-use std::net::Ipv4Addr;
+use std::net::{IpAddr, Ipv4Addr};
 use std::str::FromStr;
 use std::time::Instant;
 use tokio::net::UdpSocket;
+use udp_router_common::{
+    cookie::parse_challenge,
+    siphash::{header_tag, key_words, parse_key},
+    COOKIE_LEN,
+};
 
 #[derive(Clone, Debug, Parser)]
 struct Opt {
@@ -14,6 +19,16 @@ struct Opt {
     /// IP address of server to ping
     #[clap(long)]
     server: String,
+    /// Key of 32 hex digits to tag routing headers with, if the router requires it
+    #[clap(long)]
+    header_key: Option<String>,
+    /// Address the router receives packets from, if different from the local address
+    /// used to reach it (e.g. behind NAT)
+    #[clap(long)]
+    source_address: Option<String>,
+    /// Echo challenge cookies, if the router requires them
+    #[clap(long, default_value_t = false)]
+    challenges: bool,
 }
 
 #[tokio::main]
@@ -28,6 +43,26 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let server_ip: u32 = Ipv4Addr::from_str(&opt.server).unwrap().into();
     let payload = server_ip.to_be_bytes();
 
+    // Packets through the router carry the routing header tag, made over the address
+    // the router sees them coming from
+    let mut proxy_payload = payload.to_vec();
+    if let Some(key) = &opt.header_key {
+        let key = parse_key(key).expect("Invalid header key");
+        let source_ip: u32 = match &opt.source_address {
+            Some(addr) => Ipv4Addr::from_str(addr).unwrap().into(),
+            None => local_address(&opt.proxy).await?.into(),
+        };
+        let (k0, k1) = key_words(&key);
+        let tag = header_tag(k0, k1, server_ip, source_ip);
+        proxy_payload.extend_from_slice(&tag.to_le_bytes());
+    }
+
+    // The cookie follows the tag, and is all zeros until the router sends a challenge
+    let cookie_offset = proxy_payload.len();
+    if opt.challenges {
+        proxy_payload.extend_from_slice(&[0; COOKIE_LEN]);
+    }
+
     let mut buffer = [0_u8; 1024];
 
     println!("Measuring direct ping to server");
@@ -52,10 +87,21 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     for _ in 0..10000 {
         let start = Instant::now();
-        let _ = proxy_socket
-            .send_to(&payload, format!("{}:8888", opt.proxy))
-            .await?;
-        let _ = proxy_socket.recv_from(&mut buffer).await?;
+        loop {
+            let _ = proxy_socket
+                .send_to(&proxy_payload, format!("{}:8888", opt.proxy))
+                .await?;
+            let (len, _) = proxy_socket.recv_from(&mut buffer).await?;
+
+            // Resend the packet with the cookie of a challenge
+            match parse_challenge(&buffer[..len]) {
+                Some(cookie) if opt.challenges => {
+                    proxy_payload[cookie_offset..cookie_offset + COOKIE_LEN]
+                        .copy_from_slice(&cookie);
+                }
+                _ => break,
+            }
+        }
         let duration = start.elapsed();
         let _ = hist.record(duration.as_micros() as u64);
     }
@@ -75,3 +121,14 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+// Returns the local address packets to the router are sent from
+async fn local_address(proxy: &str) -> Result<Ipv4Addr, Box<dyn Error>> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    socket.connect(format!("{}:8888", proxy)).await?;
+
+    match socket.local_addr()?.ip() {
+        IpAddr::V4(addr) => Ok(addr),
+        IpAddr::V6(addr) => Err(format!("Unexpected IPv6 address {}", addr).into()),
+    }
+}
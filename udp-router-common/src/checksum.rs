@@ -0,0 +1,98 @@
+//
+// Internet checksum helpers. All values are in native byte order.
+//
+
+/// Value of the UDP checksum field when the sender did not compute a checksum.
+pub const UDP_NO_CHECKSUM: u16 = 0;
+
+/// Folds a 32-bit sum of 16-bit words into a one's complement checksum.
+#[inline(always)]
+pub fn fold_checksum(mut sum: u32) -> u16 {
+    // clear any overflow bits
+    sum = (sum & 0xffff) + (sum >> 16);
+    sum = (sum & 0xffff) + (sum >> 16);
+
+    !(sum as u16)
+}
+
+/// Calculates updated checksum when changing a word `old` to `new` in the checksummed
+/// data. Algorithm: https://www.rfc-editor.org/rfc/rfc1624 (eqn. 3)
+#[inline(always)]
+pub fn update_checksum(csum: u16, old: u16, new: u16) -> u16 {
+    fold_checksum((!csum) as u32 + (!old) as u32 + new as u32)
+}
+
+/// Calculates updated UDP checksum when changing a word `old` to `new` in the packet.
+///
+/// A zero checksum means the sender did not compute one and is left as is. A computed
+/// checksum of zero is sent as 0xffff, as zero is reserved (RFC 768).
+#[inline(always)]
+pub fn update_udp_checksum(csum: u16, old: u16, new: u16) -> u16 {
+    if csum == UDP_NO_CHECKSUM {
+        return UDP_NO_CHECKSUM;
+    }
+
+    match update_checksum(csum, old, new) {
+        0 => 0xffff,
+        csum => csum,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checksum(words: &[u16]) -> u16 {
+        fold_checksum(words.iter().map(|word| *word as u32).sum())
+    }
+
+    #[test]
+    fn fold_checksum_handles_carries() {
+        assert_eq!(fold_checksum(0), 0xffff);
+        assert_eq!(fold_checksum(0xffff), 0);
+        assert_eq!(fold_checksum(0x1_fffe), 0);
+        assert_eq!(fold_checksum(0xffff_ffff), 0);
+    }
+
+    #[test]
+    fn update_checksum_matches_recalculation() {
+        let mut words = [0x4500, 0x0073, 0x0000, 0x4000, 0x4011, 0xc0a8, 0x0001, 0xc0a8, 0x00c7];
+        let csum = checksum(&words);
+
+        for (old, new) in [(0xc0a8, 0x0a00), (0x0001, 0xffff), (0x00c7, 0x0000)] {
+            let index = words.iter().position(|word| *word == old).unwrap();
+            words[index] = new;
+            assert_eq!(update_checksum(csum, old, new), checksum(&words));
+            words[index] = old;
+        }
+    }
+
+    #[test]
+    fn update_checksum_handles_all_ones_checksum() {
+        // 0xffff and 0x0000 are both representations of zero in one's complement
+        assert_eq!(update_checksum(0xffff, 0x0000, 0x0001), 0xfffe);
+        assert_eq!(update_checksum(0x0000, 0x0000, 0x0001), 0xfffe);
+    }
+
+    #[test]
+    fn update_udp_checksum_keeps_missing_checksum() {
+        assert_eq!(update_udp_checksum(UDP_NO_CHECKSUM, 0x1234, 0x5678), UDP_NO_CHECKSUM);
+        assert_eq!(update_udp_checksum(UDP_NO_CHECKSUM, 0x0000, 0xffff), UDP_NO_CHECKSUM);
+    }
+
+    #[test]
+    fn update_udp_checksum_never_produces_zero() {
+        // Data summing to 0xfffe has checksum 0x0001; adding one makes the checksum zero
+        let csum = checksum(&[0xfffe]);
+        assert_eq!(csum, 0x0001);
+        assert_eq!(update_checksum(csum, 0xfffe, 0xffff), 0x0000);
+        assert_eq!(update_udp_checksum(csum, 0xfffe, 0xffff), 0xffff);
+    }
+
+    #[test]
+    fn update_udp_checksum_accepts_all_ones_checksum() {
+        // A computed checksum of zero is sent as 0xffff and must still be updated
+        assert_eq!(update_udp_checksum(0xffff, 0x0000, 0x0001), 0xfffe);
+        assert_eq!(update_udp_checksum(0xffff, 0x0001, 0x0000), 0x0001);
+    }
+}
@@ -0,0 +1,68 @@
+//
+// Challenge cookies proving that a client receives the packets sent to its address. A
+// cookie is the time it was made at in seconds of `CLOCK_MONOTONIC`, followed by
+// SipHash-2-4 of the source address of the client and that time. The router keeps no
+// state about challenged clients, it only checks that cookies are recent and authentic.
+//
+
+use crate::{siphash::siphash_2_4, CHALLENGE_LEN, CHALLENGE_MAGIC, COOKIE_LEN};
+
+/// Makes the cookie of a client at the given time. The time is sent in big-endian and
+/// the tag in little-endian byte order.
+#[inline(always)]
+pub fn make(k0: u64, k1: u64, source_ip: u32, timestamp: u32) -> [u8; COOKIE_LEN] {
+    let message = (source_ip as u64) << 32 | timestamp as u64;
+    let tag = siphash_2_4(k0, k1, u64::from_le_bytes(message.to_be_bytes()), 8);
+
+    let mut cookie = [0; COOKIE_LEN];
+    cookie[..4].copy_from_slice(&timestamp.to_be_bytes());
+    cookie[4..].copy_from_slice(&tag.to_le_bytes());
+
+    cookie
+}
+
+/// Returns the time a cookie was made at.
+#[inline(always)]
+pub fn timestamp(cookie: &[u8; COOKIE_LEN]) -> u32 {
+    u32::from_be_bytes([cookie[0], cookie[1], cookie[2], cookie[3]])
+}
+
+/// Returns the cookie of a challenge received from the router, or None if the payload
+/// is not a challenge.
+pub fn parse_challenge(payload: &[u8]) -> Option<[u8; COOKIE_LEN]> {
+    if payload.len() != CHALLENGE_LEN || payload[..4] != CHALLENGE_MAGIC.to_be_bytes() {
+        return None;
+    }
+
+    let mut cookie = [0; COOKIE_LEN];
+    cookie.copy_from_slice(&payload[4..]);
+
+    Some(cookie)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cookie_covers_address_and_time() {
+        let cookie = make(1, 2, 0xc0000201, 1000);
+        assert_eq!(timestamp(&cookie), 1000);
+        assert_eq!(make(1, 2, 0xc0000201, 1000), cookie);
+        assert_ne!(make(1, 2, 0xc0000202, 1000)[4..], cookie[4..]);
+        assert_ne!(make(1, 2, 0xc0000201, 1001)[4..], cookie[4..]);
+        assert_ne!(make(1, 3, 0xc0000201, 1000), cookie);
+    }
+
+    #[test]
+    fn parse_challenge_requires_magic() {
+        let cookie = make(1, 2, 0xc0000201, 1000);
+        let mut challenge = [0; CHALLENGE_LEN];
+        challenge[..4].copy_from_slice(&CHALLENGE_MAGIC.to_be_bytes());
+        challenge[4..].copy_from_slice(&cookie);
+        assert_eq!(parse_challenge(&challenge), Some(cookie));
+        assert_eq!(parse_challenge(&challenge[..CHALLENGE_LEN - 1]), None);
+        challenge[0] = 0xc0;
+        assert_eq!(parse_challenge(&challenge), None);
+    }
+}
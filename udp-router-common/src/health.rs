@@ -0,0 +1,63 @@
+//
+// Health check probes sent by the controller to backends. A probe is
+// [`HEALTH_PROBE`] followed by a nonce, and backends answer with [`HEALTH_REPLY`]
+// followed by the same nonce, so the controller can match answers to probes.
+//
+
+use crate::{HEALTH_PROBE, HEALTH_PROBE_LEN, HEALTH_REPLY};
+
+/// Makes the payload of a probe.
+pub fn probe(nonce: u64) -> [u8; HEALTH_PROBE_LEN] {
+    let mut probe = [0; HEALTH_PROBE_LEN];
+    probe[..4].copy_from_slice(&HEALTH_PROBE.to_be_bytes());
+    probe[4..].copy_from_slice(&nonce.to_be_bytes());
+
+    probe
+}
+
+/// Returns the answer to a probe, or None if the payload is not a probe.
+pub fn answer(payload: &[u8]) -> Option<[u8; HEALTH_PROBE_LEN]> {
+    if payload.len() != HEALTH_PROBE_LEN || payload[..4] != HEALTH_PROBE.to_be_bytes() {
+        return None;
+    }
+
+    let mut reply = [0; HEALTH_PROBE_LEN];
+    reply[..4].copy_from_slice(&HEALTH_REPLY.to_be_bytes());
+    reply[4..].copy_from_slice(&payload[4..]);
+
+    Some(reply)
+}
+
+/// Returns the nonce of the probe a payload answers, or None if the payload is not an
+/// answer.
+pub fn parse_reply(payload: &[u8]) -> Option<u64> {
+    if payload.len() != HEALTH_PROBE_LEN || payload[..4] != HEALTH_REPLY.to_be_bytes() {
+        return None;
+    }
+
+    let mut nonce = [0; 8];
+    nonce.copy_from_slice(&payload[4..]);
+
+    Some(u64::from_be_bytes(nonce))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_echoes_nonce() {
+        let reply = answer(&probe(0x0102030405060708)).unwrap();
+        assert_eq!(parse_reply(&reply), Some(0x0102030405060708));
+    }
+
+    #[test]
+    fn only_probes_are_answered() {
+        let probe = probe(7);
+        assert_eq!(answer(&probe[..HEALTH_PROBE_LEN - 1]), None);
+        assert_eq!(parse_reply(&probe), None);
+
+        let reply = answer(&probe).unwrap();
+        assert_eq!(answer(&reply), None);
+    }
+}
@@ -0,0 +1,134 @@
+//
+// HyperLogLog (https://algo.inria.fr/flajolet/Publications/FlFuGaMe07.pdf) sketches of
+// the number of distinct clients. The hash of an address picks a register by its top
+// [`HLL_PRECISION`] bits, and the register keeps the highest rank, i.e. the position of
+// the first set bit, of the remaining bits of the hashes falling into it. Sketches of
+// different CPUs are merged by taking the highest rank of each register.
+//
+// The estimate takes floating point math the XDP program has no use for, and is only
+// built for the controller.
+//
+
+use crate::{maglev::fmix64, ClientRegisters, HLL_PRECISION};
+
+// Seed of the hash of addresses, so that registers do not follow the Maglev hash
+const SEED: u64 = 0x2545f4914f6cdd1d;
+
+/// Records the address in the registers.
+#[inline(always)]
+pub fn add(registers: &mut ClientRegisters, addr: u32) {
+    let hash = fmix64(addr as u64 ^ SEED);
+    let index = (hash >> (64 - HLL_PRECISION)) as usize;
+    // The sentinel bit caps the rank for hashes whose remaining bits are all zeros
+    let rank = ((hash << HLL_PRECISION) | 1 << (HLL_PRECISION - 1)).leading_zeros() as u8 + 1;
+
+    let register = &mut registers.registers[index];
+    if rank > *register {
+        *register = rank;
+    }
+}
+
+/// Merges the registers of `other` into `registers`, as if all addresses recorded in
+/// either had been recorded in `registers`.
+pub fn merge(registers: &mut ClientRegisters, other: &ClientRegisters) {
+    for (register, &rank) in registers.registers.iter_mut().zip(other.registers.iter()) {
+        if rank > *register {
+            *register = rank;
+        }
+    }
+}
+
+/// Estimates the number of distinct addresses recorded in the registers. Small numbers,
+/// for which the raw estimate is biased, are estimated by linear counting of the empty
+/// registers instead.
+#[cfg(feature = "user")]
+pub fn estimate(registers: &ClientRegisters) -> u64 {
+    let m = crate::HLL_REGISTERS as f64;
+    let alpha = 0.7213 / (1.0 + 1.079 / m);
+    let sum: f64 = registers
+        .registers
+        .iter()
+        .map(|&rank| 1.0 / (1u64 << rank) as f64)
+        .sum();
+    let estimate = alpha * m * m / sum;
+
+    let empty = registers.registers.iter().filter(|&&rank| rank == 0).count();
+    if estimate <= 2.5 * m && empty != 0 {
+        return (m * (m / empty as f64).ln()).round() as u64;
+    }
+    estimate.round() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HLL_REGISTERS;
+
+    #[test]
+    fn adding_again_changes_nothing() {
+        let mut registers = ClientRegisters::EMPTY;
+        for addr in 0..1000 {
+            add(&mut registers, addr);
+        }
+        let before = registers;
+        for addr in 0..1000 {
+            add(&mut registers, addr);
+        }
+
+        assert_eq!(registers.registers, before.registers);
+        assert!(registers.registers.iter().all(|&rank| rank as u32 <= 65 - HLL_PRECISION));
+    }
+
+    #[test]
+    fn merge_equals_adding_to_one() {
+        let mut all = ClientRegisters::EMPTY;
+        let mut first = ClientRegisters::EMPTY;
+        let mut second = ClientRegisters::EMPTY;
+        for addr in 0..5000 {
+            add(&mut all, addr);
+            match addr % 2 {
+                0 => add(&mut first, addr),
+                _ => add(&mut second, addr),
+            }
+        }
+        merge(&mut first, &second);
+
+        assert_eq!(first.registers, all.registers);
+        let used = all.registers.iter().filter(|&&rank| rank != 0).count();
+        assert!(used > HLL_REGISTERS / 2);
+    }
+
+    #[cfg(feature = "user")]
+    #[test]
+    fn estimate_of_empty_registers_is_zero() {
+        assert_eq!(estimate(&ClientRegisters::EMPTY), 0);
+    }
+
+    #[cfg(feature = "user")]
+    #[test]
+    fn small_sets_are_linear_counted() {
+        let mut registers = ClientRegisters::EMPTY;
+        for addr in 0..100 {
+            add(&mut registers, addr);
+        }
+
+        // Some of the addresses share a register, which the raw estimate would not
+        // correct for
+        let used = registers.registers.iter().filter(|&&rank| rank != 0).count();
+        assert!(used < 100);
+        assert!((97..=103).contains(&estimate(&registers)));
+    }
+
+    #[cfg(feature = "user")]
+    #[test]
+    fn large_sets_are_within_expected_error() {
+        let mut registers = ClientRegisters::EMPTY;
+        for addr in 0..1_000_000 {
+            add(&mut registers, addr);
+        }
+
+        // The standard error is 1.04 / sqrt(HLL_REGISTERS), about 1.6%
+        let estimate = estimate(&registers);
+        assert!((950_000..=1_050_000).contains(&estimate), "{}", estimate);
+    }
+}
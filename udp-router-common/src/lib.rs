@@ -1 +1,651 @@
 #![no_std]
+
+#[cfg(feature = "user")]
+extern crate std;
+
+pub mod checksum;
+pub mod client_token;
+pub mod cookie;
+pub mod health;
+pub mod hyperloglog;
+pub mod maglev;
+pub mod siphash;
+pub mod sketch;
+
+//
+// Interfaces
+//
+// Per-interface maps in the XDP program are indexed by the side of the router the
+// interface faces. In single-interface mode only the client side is used.
+//
+
+/// Index of the client-facing interface in per-interface maps.
+pub const CLIENT_INTERFACE: u32 = 0;
+/// Index of the backend-facing interface in per-interface maps.
+pub const BACKEND_INTERFACE: u32 = 1;
+/// Number of entries in per-interface maps.
+pub const NUM_INTERFACES: u32 = 2;
+
+//
+// Counters
+//
+// Indices into the per-CPU `COUNTERS` array of the XDP program.
+//
+
+/// Non-unicast frames dropped before forwarding.
+pub const COUNTER_NON_UNICAST_DROPPED: u32 = 0;
+/// Packets with IPv4 header options dropped by policy.
+pub const COUNTER_IP_OPTIONS_DROPPED: u32 = 1;
+/// Packets without UDP checksum that were given one.
+pub const COUNTER_UDP_CHECKSUM_ADDED: u32 = 2;
+/// Packets whose IPv4 total length disagrees with the frame or header length.
+pub const COUNTER_IP_LENGTH_DROPPED: u32 = 3;
+/// Packets whose UDP length disagrees with the IPv4 total length.
+pub const COUNTER_UDP_LENGTH_DROPPED: u32 = 4;
+/// Packets whose payload is shorter than the minimum routing header.
+pub const COUNTER_SHORT_ROUTING_HEADER_DROPPED: u32 = 5;
+/// Packets dropped as their TTL expired, without notifying the sender.
+pub const COUNTER_TTL_EXPIRED_DROPPED: u32 = 6;
+/// ICMP Time Exceeded messages sent for packets whose TTL expired.
+pub const COUNTER_ICMP_TIME_EXCEEDED_SENT: u32 = 7;
+/// IPv4 fragments dropped by policy.
+pub const COUNTER_FRAGMENTS_DROPPED: u32 = 8;
+/// IPv4 fragments passed to the kernel by policy.
+pub const COUNTER_FRAGMENTS_PASSED: u32 = 9;
+/// First IPv4 fragments forwarded by policy.
+pub const COUNTER_FIRST_FRAGMENTS_FORWARDED: u32 = 10;
+/// Packets whose target address is unspecified (0.0.0.0/8).
+pub const COUNTER_TARGET_UNSPECIFIED_DROPPED: u32 = 11;
+/// Packets whose target address is a loopback address.
+pub const COUNTER_TARGET_LOOPBACK_DROPPED: u32 = 12;
+/// Packets whose target address is a multicast address.
+pub const COUNTER_TARGET_MULTICAST_DROPPED: u32 = 13;
+/// Packets whose target address is the limited broadcast address.
+pub const COUNTER_TARGET_BROADCAST_DROPPED: u32 = 14;
+/// Packets whose target address is a link-local address.
+pub const COUNTER_TARGET_LINK_LOCAL_DROPPED: u32 = 15;
+/// Packets whose target address is inside the local network.
+pub const COUNTER_TARGET_LOCAL_NETWORK_DROPPED: u32 = 16;
+/// Packets whose target address is the router's own address.
+pub const COUNTER_TARGET_ROUTER_DROPPED: u32 = 17;
+/// Packets dropped as their source address is in the client blocklist.
+pub const COUNTER_BLOCKLIST_DROPPED: u32 = 18;
+/// Packets dropped as their client exceeded its rate limit.
+pub const COUNTER_RATE_LIMITED_DROPPED: u32 = 19;
+/// Packets shed as their backend exceeded its rate limit.
+pub const COUNTER_BACKEND_OVERLOAD_DROPPED: u32 = 20;
+/// Packets dropped as their routing header tag is missing or invalid.
+pub const COUNTER_HEADER_AUTH_DROPPED: u32 = 21;
+/// Packets dropped as their client token could not be made or decoded.
+pub const COUNTER_CLIENT_TOKEN_DROPPED: u32 = 22;
+/// Responses dropped as their client was not seen recently.
+pub const COUNTER_UNSEEN_CLIENT_DROPPED: u32 = 23;
+/// Responses dropped as they exceeded the amplification limit of their client.
+pub const COUNTER_AMPLIFICATION_DROPPED: u32 = 24;
+/// Challenges sent to clients without a valid cookie.
+pub const COUNTER_CHALLENGES_SENT: u32 = 25;
+/// Packets forwarded with a valid cookie.
+pub const COUNTER_COOKIES_VERIFIED: u32 = 26;
+/// Packets dropped as they are too short to hold a cookie, or no cookie key is set.
+pub const COUNTER_COOKIE_DROPPED: u32 = 27;
+/// Packets dropped as their service is unknown or has no backends.
+pub const COUNTER_SERVICE_UNAVAILABLE_DROPPED: u32 = 28;
+/// Backends of services skipped as they are down, draining or disabled, counted once per
+/// skipped lookup.
+pub const COUNTER_UNAVAILABLE_BACKEND_SKIPPED: u32 = 29;
+/// Packets sent to the new address of a backend that moved.
+pub const COUNTER_BACKEND_REMAPPED: u32 = 30;
+/// Number of entries in the `COUNTERS` array.
+pub const NUM_COUNTERS: u32 = 64;
+
+//
+// Protocol
+//
+
+/// Length of the routing header, i.e. the IPv4 address at the start of the payload.
+pub const ROUTING_HEADER_LEN: usize = 4;
+
+//
+// Services
+//
+// Routing headers in `SERVICE_NET`/8, which is reserved and never the address of a
+// host, name a service by the lower 24 bits instead of a backend. The backend of each
+// packet is looked up from the table of the service in the `SERVICE_TABLES` array of
+// the XDP program, at index `service_id * MAGLEV_TABLE_SIZE` plus the
+// [`maglev::flow_hash`] of the client modulo the table size. Entries hold backend
+// addresses in host byte order, or zero if the service has no backends.
+//
+// Packets forwarded to each backend through a service are counted in the per-CPU
+// `SERVICE_BACKEND_PACKETS` map of the XDP program, keyed by address in host byte
+// order, showing how traffic is actually split between backends and canaries.
+//
+
+/// Network of routing headers naming a service.
+pub const SERVICE_NET: u32 = 0xf0000000;
+/// Mask of the network of routing headers naming a service.
+pub const SERVICE_MASK: u32 = 0xff000000;
+/// Number of service IDs, starting from zero.
+pub const MAX_SERVICES: u32 = 256;
+/// Maximum number of backends of a service.
+pub const MAX_SERVICE_BACKENDS: usize = 64;
+/// Number of entries in the lookup table of a service. Prime, and large compared to
+/// the number of backends, so that backends get close to equal shares.
+pub const MAGLEV_TABLE_SIZE: usize = 4093;
+/// Maximum number of backends whose packets through services are counted. Least
+/// recently used counters are evicted.
+pub const MAX_SERVICE_BACKEND_COUNTERS: u32 = 4096;
+
+//
+// Health checks
+//
+// The controller probes backends with packets made by [`health::probe`], sent directly
+// to the backends with [`HEALTH_PROBE`] as routing header. Backends answer with
+// [`HEALTH_REPLY`] in its place, which the router passes to the controller instead of
+// forwarding. Backends failing their probes are marked down in the `BACKEND_STATES`
+// map of the XDP program, keyed by address in host byte order, and packets to services
+// skip them. Backends without an entry are up.
+//
+// Backends can also be taken out of services for maintenance. Draining backends only
+// receive packets of clients they already serve, and disabled backends receive no
+// packets through services.
+//
+
+/// Routing header of health check probes. Reserved, so it is never a client address.
+pub const HEALTH_PROBE: u32 = 0xfffffffe;
+/// Routing header of answers to health check probes.
+pub const HEALTH_REPLY: u32 = 0xfffffffd;
+/// Length of the payload of probes and their answers, i.e. the routing header followed
+/// by a nonce.
+pub const HEALTH_PROBE_LEN: usize = ROUTING_HEADER_LEN + 8;
+/// Maximum number of backends with a health state.
+pub const MAX_HEALTH_CHECKED_BACKENDS: u32 = 4096;
+
+/// State of a backend receiving packets.
+pub const BACKEND_UP: u32 = 0;
+/// State of a backend failing health checks, receiving no packets through services.
+pub const BACKEND_DOWN: u32 = 1;
+/// State of a backend only receiving packets of clients it already serves.
+pub const BACKEND_DRAINING: u32 = 2;
+/// State of a backend receiving no packets through services.
+pub const BACKEND_DISABLED: u32 = 3;
+
+//
+// Session affinity
+//
+// The backend each client flow of a service was sent to is remembered in the
+// `SERVICE_FLOWS` map of the XDP program, keyed by [`flow_key`], so clients stay with
+// their backend when the pools of the service change. A flow is sent to a new backend
+// once it has been idle for the configured timeout, or its backend is down or disabled.
+// Pinned flows stay with their backend however long they are idle.
+//
+
+/// Maximum number of client flows whose backend is remembered. Least recently used flows
+/// are evicted, including pinned flows.
+pub const MAX_SERVICE_FLOWS: u32 = 262144;
+/// Time in nanoseconds after its last packet a flow forgets its backend, unless
+/// configured otherwise.
+pub const DEFAULT_FLOW_IDLE_TIMEOUT_NS: u64 = 60_000_000_000;
+
+/// Backend a client flow of a service was last sent to.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct FlowAffinity {
+    /// Address of the backend in host byte order.
+    pub backend: u32,
+    /// ID of the service the flow was sent to.
+    pub service_id: u16,
+    /// Non-zero if the flow stays with the backend however long it is idle.
+    pub pinned: u16,
+    /// Time of the last packet of the flow in nanoseconds of `CLOCK_MONOTONIC`.
+    pub last_seen: u64,
+}
+
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for FlowAffinity {}
+
+/// Key of a client flow in the `SERVICE_FLOWS` map, from the client address and port in
+/// host byte order.
+#[inline(always)]
+pub fn flow_key(client_ip: u32, client_port: u16) -> u64 {
+    (client_ip as u64) << 16 | client_port as u64
+}
+
+//
+// Backend remapping
+//
+// Backends that moved to another host, e.g. in a live migration, are reached at their
+// new address by clients still sending the old one. The `BACKEND_REMAPS` map of the XDP
+// program is keyed by the old address in host byte order. Remaps apply to backends
+// looked up from services as well.
+//
+
+/// Maximum number of remapped backends.
+pub const MAX_BACKEND_REMAPS: u32 = 4096;
+
+/// New address of a backend.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct BackendRemap {
+    /// Time the remap expires at in nanoseconds of `CLOCK_MONOTONIC`, or zero if never.
+    pub expires_at: u64,
+    /// Number of packets sent to the new address.
+    pub hits: u64,
+    /// New address of the backend in host byte order.
+    pub backend: u32,
+    /// Zero. Keeps the struct free of padding.
+    pub padding: u32,
+}
+
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for BackendRemap {}
+
+//
+// Traffic accounting
+//
+// Packets and bytes forwarded to and from each backend are counted in the per-CPU
+// `BACKEND_TRAFFIC` map of the XDP program, keyed by backend address in host byte order.
+// If a client prefix length is configured, they are also counted per client prefix in
+// the per-CPU `CLIENT_PREFIX_TRAFFIC` map, keyed by the network address of the prefix in
+// host byte order.
+//
+// Neither map evicts entries by itself, so that counts are never lost while traffic
+// flows. The controller removes entries that have been idle for a while. Traffic of new
+// backends or prefixes is not counted while their map is full.
+//
+
+/// Maximum number of backends whose traffic is counted.
+pub const MAX_TRAFFIC_BACKENDS: u32 = 4096;
+/// Maximum number of client prefixes whose traffic is counted.
+pub const MAX_TRAFFIC_CLIENT_PREFIXES: u32 = 65536;
+
+/// Traffic of a backend or client prefix on one CPU.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct TrafficCounter {
+    /// Packets forwarded from clients to backends.
+    pub requests: u64,
+    /// Bytes of packets forwarded from clients to backends.
+    pub request_bytes: u64,
+    /// Packets forwarded from backends to clients.
+    pub responses: u64,
+    /// Bytes of packets forwarded from backends to clients.
+    pub response_bytes: u64,
+    /// Time of the last packet on the CPU in nanoseconds of `CLOCK_MONOTONIC`.
+    pub last_seen: u64,
+}
+
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for TrafficCounter {}
+
+//
+// Heavy hitters
+//
+// Packets from each client and to each backend are counted in count-min sketches in the
+// per-CPU `HEAVY_HITTERS` array of the XDP program, indexed by [`SKETCH_SOURCES`] and
+// [`SKETCH_TARGETS`], to find the addresses sending or receiving the most packets
+// without keeping state per address. See [`sketch`] for how they are updated. The
+// controller reads and clears the sketches once per window.
+//
+
+/// Number of rows of a sketch, each with its own hash of addresses.
+pub const SKETCH_DEPTH: usize = 4;
+/// Number of counters in each row of a sketch. A power of two.
+pub const SKETCH_WIDTH: usize = 1024;
+/// Number of addresses with the highest counts a sketch keeps as candidates.
+pub const SKETCH_CANDIDATES: usize = 16;
+/// Index of the sketch of client addresses in the `HEAVY_HITTERS` array.
+pub const SKETCH_SOURCES: u32 = 0;
+/// Index of the sketch of backend addresses in the `HEAVY_HITTERS` array.
+pub const SKETCH_TARGETS: u32 = 1;
+/// Number of entries in the `HEAVY_HITTERS` array.
+pub const NUM_SKETCHES: u32 = 2;
+
+/// Address with one of the highest counts in a sketch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Candidate {
+    /// Address in host byte order.
+    pub addr: u32,
+    /// Estimated packets of the address, or zero if the slot is empty.
+    pub packets: u32,
+}
+
+/// Count-min sketch of the packets of addresses on one CPU.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct HeavyHitterSketch {
+    /// Counters of packets, [`SKETCH_WIDTH`] per row.
+    pub counters: [u32; SKETCH_DEPTH * SKETCH_WIDTH],
+    /// Addresses with the highest counts, in no particular order.
+    pub candidates: [Candidate; SKETCH_CANDIDATES],
+}
+
+impl HeavyHitterSketch {
+    /// Sketch without any packets counted.
+    pub const EMPTY: HeavyHitterSketch = HeavyHitterSketch {
+        counters: [0; SKETCH_DEPTH * SKETCH_WIDTH],
+        candidates: [Candidate {
+            addr: 0,
+            packets: 0,
+        }; SKETCH_CANDIDATES],
+    };
+}
+
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for HeavyHitterSketch {}
+
+//
+// Unique clients
+//
+// Distinct clients the router forwards packets from or to are counted in a HyperLogLog
+// sketch in the per-CPU `CLIENT_REGISTERS` array of the XDP program, updated with
+// [`hyperloglog::add`]. The controller merges the registers of all CPUs, estimates the
+// number of clients and clears the registers once per window.
+//
+
+/// Number of bits of the hash of an address picking its register.
+pub const HLL_PRECISION: u32 = 12;
+/// Number of registers of a sketch.
+pub const HLL_REGISTERS: usize = 1 << HLL_PRECISION;
+
+/// HyperLogLog registers of the clients seen on one CPU.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ClientRegisters {
+    /// Highest rank of the hashes of the addresses falling into each register.
+    pub registers: [u8; HLL_REGISTERS],
+}
+
+impl ClientRegisters {
+    /// Registers without any clients.
+    pub const EMPTY: ClientRegisters = ClientRegisters {
+        registers: [0; HLL_REGISTERS],
+    };
+}
+
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for ClientRegisters {}
+
+//
+// Keys
+//
+// Routing header tags and client tokens are keyed with SipHash keys, held in arrays of
+// the XDP program with the key in use and the key it replaced. While keys are rotated,
+// values made with the previous key are accepted as well.
+//
+
+/// Index of the key new values are made with in key arrays.
+pub const KEY_CURRENT: u32 = 0;
+/// Index of the key accepted until its values have been replaced.
+pub const KEY_PREVIOUS: u32 = 1;
+/// Number of entries in key arrays.
+pub const NUM_KEYS: u32 = 2;
+
+/// SipHash key.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct SipKey {
+    /// First word of the key, as split by [`siphash::key_words`].
+    pub k0: u64,
+    /// Second word of the key.
+    pub k1: u64,
+    /// Non-zero if the key is in use.
+    pub active: u64,
+}
+
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for SipKey {}
+
+//
+// Header authentication
+//
+// Clients can be required to follow the routing header with a tag authenticating it,
+// calculated by [`siphash::header_tag`] with a key from the `HEADER_KEYS` array of the
+// XDP program. The router strips the tag before forwarding the packet to the backend.
+//
+
+/// Length of the tag following the routing header in bytes.
+pub const HEADER_TAG_LEN: usize = 8;
+
+//
+// Client tokens
+//
+// The router can hide the addresses of clients from backends by writing a token made
+// by [`client_token::encode`] into the routing header instead of the address, with a
+// key from the `CLIENT_TOKEN_KEYS` array of the XDP program. Backends send responses
+// with the token as routing header, which the router decodes back into the address.
+//
+
+/// Length of the routing header holding a client token in bytes.
+pub const CLIENT_TOKEN_LEN: usize = 8;
+
+//
+// Reflection protection
+//
+// Backends can only send responses to clients that sent packets through the router
+// recently, so the router cannot be used to send traffic to arbitrary hosts. The time
+// each client was last seen is kept in the `SEEN_CLIENTS` map of the XDP program, keyed
+// by address in host byte order.
+//
+
+/// Maximum number of clients remembered. Least recently seen clients are evicted.
+pub const MAX_SEEN_CLIENTS: u32 = 65536;
+
+//
+// Amplification limiting
+//
+// The bytes of requests from and responses to each client are counted in the
+// `CLIENT_TRAFFIC` map of the XDP program, keyed by address in host byte order.
+// Responses are dropped once they exceed the configured multiple of the requests. The
+// counts are halved every [`AMPLIFICATION_HALF_LIFE_NS`], so that past requests do not
+// allow large responses forever.
+//
+
+/// Maximum number of clients whose traffic is counted. Least recently active clients are
+/// evicted.
+pub const MAX_AMPLIFICATION_CLIENTS: u32 = 65536;
+/// Time in nanoseconds after which the byte counts of a client are halved.
+pub const AMPLIFICATION_HALF_LIFE_NS: u64 = 10_000_000_000;
+/// Maximum ratio of response to request bytes that can be configured.
+pub const MAX_AMPLIFICATION: u64 = 1000;
+
+/// Bytes of requests from and responses to a client.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ClientTraffic {
+    /// Bytes of requests forwarded from the client.
+    pub request_bytes: u64,
+    /// Bytes of responses forwarded to the client.
+    pub response_bytes: u64,
+    /// Time the counts were last halved at in nanoseconds of `CLOCK_MONOTONIC`.
+    pub decayed_at: u64,
+}
+
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for ClientTraffic {}
+
+//
+// Challenges
+//
+// Clients can be required to prove that they receive packets sent to their address,
+// so that floods with spoofed source addresses never reach backends. Packets from
+// clients carry a cookie after the routing header and its tag, if any. Packets without
+// a valid cookie are answered with a challenge holding a new cookie, made by
+// [`cookie::make`] with a key from the `COOKIE_KEYS` array of the XDP program, which
+// the client echoes in later packets. The router strips the cookie before forwarding
+// the packet to the backend.
+//
+// Clients send zeros in place of the cookie until challenged, so that challenges are
+// never longer than the packets they answer.
+//
+
+/// Length of a cookie in bytes.
+pub const COOKIE_LEN: usize = 12;
+/// Length of the payload of a challenge, i.e. [`CHALLENGE_MAGIC`] followed by a cookie.
+pub const CHALLENGE_LEN: usize = 4 + COOKIE_LEN;
+/// Start of the payload of a challenge, in place of the routing header of a response.
+/// This is the limited broadcast address, which is never the address of a backend.
+pub const CHALLENGE_MAGIC: u32 = 0xffffffff;
+/// Time in seconds a cookie is accepted for. The cookie key is replaced at the same
+/// interval, with the previous key accepted until the next replacement.
+pub const COOKIE_LIFETIME_SECS: u64 = 120;
+
+//
+// Blocklist
+//
+// Client prefixes whose traffic is dropped. The `BLOCKLIST` LPM trie of the XDP program
+// is keyed by address in network byte order.
+//
+
+/// Maximum number of prefixes in the blocklist.
+pub const MAX_BLOCKLIST_ENTRIES: u32 = 65536;
+
+/// Blocklist entry of a client prefix.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct BlocklistEntry {
+    /// Time the entry expires at in nanoseconds of `CLOCK_MONOTONIC`, or zero if never.
+    pub expires_at: u64,
+    /// Number of packets dropped due to the entry.
+    pub hits: u64,
+}
+
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for BlocklistEntry {}
+
+//
+// Rate limiting
+//
+// Packets from each client are limited by a token bucket in the `CLIENT_BUCKETS` map of
+// the XDP program. The limits of a client are looked up from the `CLIENT_RATE_LIMITS`
+// LPM trie, keyed by address in network byte order, falling back to the global limits
+// in the `CONFIG` array.
+//
+// Packets to each backend are likewise limited by a token bucket in `BACKEND_BUCKETS`,
+// with limits from the `BACKEND_RATE_LIMITS` LPM trie. Backends without a limit are
+// unlimited.
+//
+
+/// Maximum number of clients with a token bucket. Least recently used buckets are evicted.
+pub const MAX_RATE_LIMITED_CLIENTS: u32 = 65536;
+/// Maximum number of backends with a token bucket. Least recently used buckets are
+/// evicted.
+pub const MAX_RATE_LIMITED_BACKENDS: u32 = 4096;
+/// Maximum number of prefixes with their own rate limit, for clients and backends each.
+pub const MAX_PREFIX_RATE_LIMITS: u32 = 4096;
+/// Tokens are kept in units of 1/`TOKEN_SCALE` packets, so that buckets can be refilled
+/// with nanosecond precision.
+pub const TOKEN_SCALE: u64 = 1_000_000_000;
+/// Maximum rate and burst of a rate limit in packets, keeping bucket capacities in
+/// tokens from overflowing.
+pub const MAX_RATE_LIMIT: u64 = 1_000_000_000;
+
+/// Rate limit of the clients in a prefix.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimit {
+    /// Packets per second, or zero if unlimited.
+    pub rate: u64,
+    /// Maximum number of packets in a burst. Zero means the same as `rate`.
+    pub burst: u64,
+}
+
+/// Token bucket of a client or backend.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct TokenBucket {
+    /// Tokens left in units of 1/[`TOKEN_SCALE`] packets.
+    pub tokens: u64,
+    /// Time the bucket was last refilled at in nanoseconds of `CLOCK_MONOTONIC`.
+    pub updated_at: u64,
+    /// Number of packets dropped as the bucket was empty.
+    pub dropped: u64,
+}
+
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for RateLimit {}
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for TokenBucket {}
+
+//
+// Configuration
+//
+// Indices into the `CONFIG` array of the XDP program. Entries the controller has not
+// set read as zero, so the zero value of each setting is its default.
+//
+
+/// Handling of IPv4 packets with header options, one of the `IP_OPTIONS_*` values.
+pub const CONFIG_IP_OPTIONS_POLICY: u32 = 0;
+/// Handling of UDP packets without checksum, one of the `UDP_ZERO_CHECKSUM_*` values.
+pub const CONFIG_UDP_ZERO_CHECKSUM_POLICY: u32 = 1;
+/// Minimum length of the routing header at the start of the UDP payload in bytes.
+/// Values below [`ROUTING_HEADER_LEN`] mean [`ROUTING_HEADER_LEN`].
+pub const CONFIG_MIN_ROUTING_HEADER_LEN: u32 = 2;
+/// Handling of packets arriving with a TTL of 1 or less, one of the `TTL_EXPIRED_*`
+/// values.
+pub const CONFIG_TTL_EXPIRED_POLICY: u32 = 3;
+/// Handling of IPv4 fragments, one of the `FRAGMENTS_*` values.
+pub const CONFIG_FRAGMENT_POLICY: u32 = 4;
+/// Packets per second allowed from each client without a prefix rate limit, or zero if
+/// unlimited.
+pub const CONFIG_CLIENT_RATE_LIMIT: u32 = 5;
+/// Maximum burst of packets from each client without a prefix rate limit. Zero means the
+/// same as [`CONFIG_CLIENT_RATE_LIMIT`].
+pub const CONFIG_CLIENT_BURST: u32 = 6;
+/// Authentication of routing headers from clients, one of the `HEADER_AUTH_*` values.
+pub const CONFIG_HEADER_AUTH_POLICY: u32 = 7;
+/// Identification of clients to backends, one of the `CLIENT_TOKENS_*` values.
+pub const CONFIG_CLIENT_TOKEN_POLICY: u32 = 8;
+/// Time in nanoseconds responses are forwarded to a client after its last packet, or
+/// zero if responses are forwarded to any client.
+pub const CONFIG_SEEN_CLIENT_WINDOW: u32 = 9;
+/// Maximum number of response bytes forwarded to a client per request byte from it, or
+/// zero if unlimited.
+pub const CONFIG_MAX_AMPLIFICATION: u32 = 10;
+/// Whether clients must echo a challenge cookie, one of the `CHALLENGES_*` values.
+pub const CONFIG_CHALLENGE_POLICY: u32 = 11;
+/// Time in nanoseconds after its last packet a client flow forgets its backend. Zero
+/// means [`DEFAULT_FLOW_IDLE_TIMEOUT_NS`].
+pub const CONFIG_FLOW_IDLE_TIMEOUT: u32 = 12;
+/// Length of the client prefixes traffic is counted by, or zero if traffic is only
+/// counted by backend. Values above 32 mean 32.
+pub const CONFIG_TRAFFIC_CLIENT_PREFIX_LEN: u32 = 13;
+/// Number of entries in the `CONFIG` array.
+pub const NUM_CONFIG_ENTRIES: u32 = 32;
+
+/// Forward packets with IPv4 header options, leaving the options untouched.
+pub const IP_OPTIONS_FORWARD: u64 = 0;
+/// Drop packets with IPv4 header options.
+pub const IP_OPTIONS_DROP: u64 = 1;
+
+/// Forward UDP packets without checksum as they are.
+pub const UDP_ZERO_CHECKSUM_KEEP: u64 = 0;
+/// Calculate a full checksum for UDP packets without checksum.
+pub const UDP_ZERO_CHECKSUM_CALCULATE: u64 = 1;
+
+/// Drop packets whose TTL expired.
+pub const TTL_EXPIRED_DROP: u64 = 0;
+/// Answer packets whose TTL expired with an ICMP Time Exceeded message.
+pub const TTL_EXPIRED_ICMP: u64 = 1;
+
+/// Drop IPv4 fragments.
+pub const FRAGMENTS_DROP: u64 = 0;
+/// Pass IPv4 fragments to the kernel.
+pub const FRAGMENTS_PASS: u64 = 1;
+/// Forward first fragments, which carry the UDP and routing headers, and drop the rest.
+pub const FRAGMENTS_FORWARD_FIRST: u64 = 2;
+
+/// Forward packets from clients without a routing header tag.
+pub const HEADER_AUTH_DISABLED: u64 = 0;
+/// Drop packets from clients without a valid routing header tag.
+pub const HEADER_AUTH_REQUIRED: u64 = 1;
+
+/// Identify clients to backends by their address.
+pub const CLIENT_TOKENS_DISABLED: u64 = 0;
+/// Identify clients to backends by a token.
+pub const CLIENT_TOKENS_ENABLED: u64 = 1;
+
+/// Forward packets from clients without a cookie.
+pub const CHALLENGES_DISABLED: u64 = 0;
+/// Answer packets from clients without a valid cookie with a challenge.
+pub const CHALLENGES_REQUIRED: u64 = 1;
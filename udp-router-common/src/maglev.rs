@@ -0,0 +1,199 @@
+//
+// Maglev consistent hashing (https://research.google/pubs/pub44824/) of clients onto
+// the backends of a service. The controller fills a lookup table per service, and the
+// XDP program picks the entry of a packet by the hash of its client address and port.
+// When a backend joins or leaves the pool, few clients move between the others.
+//
+// A service may also have a pool of canary backends, which get a percentage of the
+// entries. Which entries go to canaries is fixed, so clients stay on their backend
+// while the percentage is unchanged.
+//
+
+use crate::{MAGLEV_TABLE_SIZE, MAX_SERVICE_BACKENDS};
+
+// Seeds of the hashes giving the offset and skip of the permutation of a backend
+const OFFSET_SEED: u64 = 0x9e3779b97f4a7c15;
+const SKIP_SEED: u64 = 0xc2b2ae3d27d4eb4f;
+// Seed of the hash ranking entries for canary pools
+const CANARY_SEED: u64 = 0x165667b19e3779f9;
+
+/// Hashes the address and port of a client to pick its entry in a lookup table.
+#[inline(always)]
+pub fn flow_hash(client_ip: u32, client_port: u16) -> u32 {
+    fmix64((client_ip as u64) << 16 | client_port as u64) as u32
+}
+
+/// Fills the lookup table of a service with the addresses of its backends, each taking
+/// an equal share of the entries. The table is filled with zeros if there are no
+/// backends. Returns false, leaving the table untouched, if there are more than
+/// [`MAX_SERVICE_BACKENDS`] backends.
+pub fn populate(backends: &[u32], table: &mut [u32; MAGLEV_TABLE_SIZE]) -> bool {
+    if backends.len() > MAX_SERVICE_BACKENDS {
+        return false;
+    }
+    if backends.is_empty() {
+        *table = [0; MAGLEV_TABLE_SIZE];
+        return true;
+    }
+
+    // The table must not depend on the order backends were added in
+    let mut sorted = [0; MAX_SERVICE_BACKENDS];
+    let sorted = &mut sorted[..backends.len()];
+    sorted.copy_from_slice(backends);
+    sorted.sort_unstable();
+
+    let size = MAGLEV_TABLE_SIZE as u64;
+    let mut offsets = [0; MAX_SERVICE_BACKENDS];
+    let mut skips = [0; MAX_SERVICE_BACKENDS];
+    for (i, &backend) in sorted.iter().enumerate() {
+        offsets[i] = fmix64(backend as u64 ^ OFFSET_SEED) % size;
+        skips[i] = fmix64(backend as u64 ^ SKIP_SEED) % (size - 1) + 1;
+    }
+
+    // Backends take turns claiming the next free entry of their permutation
+    let mut filled = [false; MAGLEV_TABLE_SIZE];
+    let mut next = [0; MAX_SERVICE_BACKENDS];
+    let mut count = 0;
+    while count < MAGLEV_TABLE_SIZE {
+        for (i, &backend) in sorted.iter().enumerate() {
+            let mut entry = (offsets[i] + next[i] * skips[i]) % size;
+            while filled[entry as usize] {
+                next[i] += 1;
+                entry = (offsets[i] + next[i] * skips[i]) % size;
+            }
+            table[entry as usize] = backend;
+            filled[entry as usize] = true;
+            next[i] += 1;
+            count += 1;
+            if count == MAGLEV_TABLE_SIZE {
+                break;
+            }
+        }
+    }
+
+    true
+}
+
+/// Fills the lookup table of a service like [`populate`], with `canary_percent` of the
+/// entries going to the canary backends. Entries going to canaries at a percentage
+/// still do at any higher one, so raising it only moves clients from the primary
+/// backends to canaries. If either pool is empty, the other gets all entries.
+pub fn populate_with_canaries(
+    backends: &[u32],
+    canaries: &[u32],
+    canary_percent: u32,
+    table: &mut [u32; MAGLEV_TABLE_SIZE],
+) -> bool {
+    let mut canary_table = [0; MAGLEV_TABLE_SIZE];
+    if !populate(canaries, &mut canary_table) || !populate(backends, table) {
+        return false;
+    }
+    if canaries.is_empty() {
+        return true;
+    }
+
+    for (entry, backend) in table.iter_mut().enumerate() {
+        if backends.is_empty() || is_canary_entry(entry, canary_percent) {
+            *backend = canary_table[entry];
+        }
+    }
+
+    true
+}
+
+// Returns true if the entry goes to canaries at the percentage
+#[inline(always)]
+fn is_canary_entry(entry: usize, canary_percent: u32) -> bool {
+    fmix64(entry as u64 ^ CANARY_SEED) % 100 < canary_percent as u64
+}
+
+// Finalizer of MurmurHash3, mixing all bits of the input into each bit of the output
+#[inline(always)]
+pub(crate) fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51afd7ed558ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ceb9fe1a85ec53);
+    k ^= k >> 33;
+
+    k
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BACKENDS: [u32; 5] = [0x0a000301, 0x0a000302, 0x0a000303, 0x0a000304, 0x0a000305];
+
+    fn table_of(backends: &[u32]) -> [u32; MAGLEV_TABLE_SIZE] {
+        let mut table = [0; MAGLEV_TABLE_SIZE];
+        assert!(populate(backends, &mut table));
+        table
+    }
+
+    #[test]
+    fn backends_get_equal_shares() {
+        let table = table_of(&BACKENDS);
+        for backend in BACKENDS {
+            let share = table.iter().filter(|&&entry| entry == backend).count();
+            assert!(share.abs_diff(MAGLEV_TABLE_SIZE / BACKENDS.len()) <= 1);
+        }
+    }
+
+    #[test]
+    fn table_ignores_backend_order() {
+        let mut reversed = BACKENDS;
+        reversed.reverse();
+        assert_eq!(table_of(&reversed), table_of(&BACKENDS));
+    }
+
+    #[test]
+    fn removing_backend_moves_few_entries() {
+        let table = table_of(&BACKENDS);
+        let smaller = table_of(&BACKENDS[..4]);
+        let moved = table
+            .iter()
+            .zip(smaller.iter())
+            .filter(|(&old, &new)| old != BACKENDS[4] && old != new)
+            .count();
+        assert!(moved < MAGLEV_TABLE_SIZE / 20);
+    }
+
+    #[test]
+    fn canaries_get_their_percentage() {
+        let (backends, canaries) = BACKENDS.split_at(4);
+        let mut table = [0; MAGLEV_TABLE_SIZE];
+        for percent in [0, 10, 50, 100] {
+            assert!(populate_with_canaries(
+                backends, canaries, percent, &mut table
+            ));
+            let share = table.iter().filter(|&&entry| entry == canaries[0]).count();
+            let expected = MAGLEV_TABLE_SIZE * percent as usize / 100;
+            assert!(share.abs_diff(expected) < MAGLEV_TABLE_SIZE / 50);
+        }
+    }
+
+    #[test]
+    fn raising_canary_percentage_only_moves_to_canaries() {
+        let (backends, canaries) = BACKENDS.split_at(4);
+        let mut table = [0; MAGLEV_TABLE_SIZE];
+        let mut raised = [0; MAGLEV_TABLE_SIZE];
+        assert!(populate_with_canaries(backends, canaries, 10, &mut table));
+        assert!(populate_with_canaries(backends, canaries, 20, &mut raised));
+        for (&old, &new) in table.iter().zip(raised.iter()) {
+            assert!(old == new || new == canaries[0]);
+        }
+
+        assert!(populate_with_canaries(&[], canaries, 10, &mut table));
+        assert!(table.iter().all(|&entry| entry == canaries[0]));
+    }
+
+    #[test]
+    fn populate_rejects_too_many_backends() {
+        let backends = [1; MAX_SERVICE_BACKENDS + 1];
+        let mut table = [0; MAGLEV_TABLE_SIZE];
+        assert!(!populate(&backends, &mut table));
+        assert!(populate(&[], &mut table));
+        assert!(table.iter().all(|&entry| entry == 0));
+    }
+}
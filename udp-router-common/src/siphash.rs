@@ -0,0 +1,135 @@
+//
+// SipHash-2-4 (https://cr.yp.to/siphash/siphash-20120918.pdf) of the authenticated
+// routing header. Shared by the XDP program and the clients signing their packets.
+//
+
+/// Length of SipHash keys in bytes.
+pub const KEY_LEN: usize = 16;
+
+/// Splits a key into the two words SipHash is keyed with.
+pub fn key_words(key: &[u8; KEY_LEN]) -> (u64, u64) {
+    let mut k0 = [0; 8];
+    let mut k1 = [0; 8];
+    k0.copy_from_slice(&key[..8]);
+    k1.copy_from_slice(&key[8..]);
+
+    (u64::from_le_bytes(k0), u64::from_le_bytes(k1))
+}
+
+/// Parses a key written as 32 hexadecimal digits.
+pub fn parse_key(s: &str) -> Option<[u8; KEY_LEN]> {
+    if s.len() != 2 * KEY_LEN || !s.is_ascii() {
+        return None;
+    }
+
+    let mut key = [0; KEY_LEN];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).ok()?;
+    }
+
+    Some(key)
+}
+
+/// Calculates the tag of a routing header, i.e. SipHash-2-4 of the target address
+/// followed by the source address of the packet, both in network byte order. The tag is
+/// sent in little-endian byte order.
+#[inline(always)]
+pub fn header_tag(k0: u64, k1: u64, target_ip: u32, source_ip: u32) -> u64 {
+    let target = target_ip.to_be_bytes();
+    let source = source_ip.to_be_bytes();
+    let message = u64::from_le_bytes([
+        target[0], target[1], target[2], target[3], source[0], source[1], source[2], source[3],
+    ]);
+
+    siphash_2_4(k0, k1, message, 8)
+}
+
+/// SipHash-2-4 of a message of at most 8 bytes, given as a little-endian word.
+#[inline(always)]
+pub(crate) fn siphash_2_4(k0: u64, k1: u64, message: u64, len: u8) -> u64 {
+    let mut v = [
+        k0 ^ 0x736f6d6570736575,
+        k1 ^ 0x646f72616e646f6d,
+        k0 ^ 0x6c7967656e657261,
+        k1 ^ 0x7465646279746573,
+    ];
+
+    // Messages of exactly 8 bytes are followed by a block holding only their length
+    if len == 8 {
+        compress(&mut v, message);
+        compress(&mut v, (len as u64) << 56);
+    } else {
+        compress(&mut v, message | (len as u64) << 56);
+    }
+
+    v[2] ^= 0xff;
+    for _ in 0..4 {
+        sip_round(&mut v);
+    }
+
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+#[inline(always)]
+fn compress(v: &mut [u64; 4], block: u64) {
+    v[3] ^= block;
+    sip_round(v);
+    sip_round(v);
+    v[0] ^= block;
+}
+
+#[inline(always)]
+fn sip_round(v: &mut [u64; 4]) {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13);
+    v[1] ^= v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16);
+    v[3] ^= v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21);
+    v[3] ^= v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17);
+    v[1] ^= v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Key 00 01 .. 0f of the reference test vectors
+    fn test_key() -> (u64, u64) {
+        let mut key = [0; KEY_LEN];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        key_words(&key)
+    }
+
+    #[test]
+    fn parse_key_reads_hex() {
+        let (k0, k1) = test_key();
+        let key = parse_key("000102030405060708090a0b0c0d0e0f").unwrap();
+        assert_eq!(key_words(&key), (k0, k1));
+        assert_eq!(parse_key("000102030405060708090a0b0c0d0e"), None);
+        assert_eq!(parse_key("000102030405060708090a0b0c0d0e0g"), None);
+    }
+
+    #[test]
+    fn siphash_matches_reference_vectors() {
+        let (k0, k1) = test_key();
+        assert_eq!(siphash_2_4(k0, k1, 0, 0), 0x726fdb47dd0e0e31);
+        assert_eq!(siphash_2_4(k0, k1, 0x0706050403020100, 8), 0x93f5f5799a932462);
+    }
+
+    #[test]
+    fn header_tag_covers_target_and_source() {
+        let (k0, k1) = test_key();
+        assert_eq!(header_tag(k0, k1, 0x00010203, 0x04050607), 0x93f5f5799a932462);
+        assert_ne!(header_tag(k0, k1, 0x00010203, 0x04050608), 0x93f5f5799a932462);
+        assert_ne!(header_tag(k0 ^ 1, k1, 0x00010203, 0x04050607), 0x93f5f5799a932462);
+    }
+}
@@ -0,0 +1,126 @@
+//
+// Count-min sketches (https://doi.org/10.1016/j.jalgor.2003.12.001) of the packets of
+// heavy hitters. An address is counted in one counter of each row of a sketch, picked
+// by a hash of the address seeded per row, and its packets are estimated by the
+// smallest of these counters. Estimates never fall short of the actual count, and are
+// unlikely to exceed it by more than a small fraction of all packets counted.
+//
+// Sketches cannot list the addresses they counted, so each keeps the addresses with the
+// highest estimates as candidates. The controller merges the sketches of all CPUs and
+// estimates the candidates again from the merged counters.
+//
+
+use crate::{maglev::fmix64, Candidate, HeavyHitterSketch, SKETCH_DEPTH, SKETCH_WIDTH};
+
+// Seeds of the hashes of the rows
+const ROW_SEEDS: [u64; SKETCH_DEPTH] = [
+    0x8ebc6af09c88c6e3,
+    0x589965cc75374cc3,
+    0x1d8e4e27c47d124f,
+    0xa0761d6478bd642f,
+];
+
+/// Returns the index of the counter of the address in a row.
+#[inline(always)]
+pub fn counter_index(addr: u32, row: usize) -> usize {
+    let column = fmix64(addr as u64 ^ ROW_SEEDS[row]) as usize & (SKETCH_WIDTH - 1);
+    row * SKETCH_WIDTH + column
+}
+
+/// Counts a packet of the address, keeping the address as a candidate if its estimate
+/// is above that of the lowest candidate.
+#[inline(always)]
+pub fn count(sketch: &mut HeavyHitterSketch, addr: u32) {
+    let mut estimate = u32::MAX;
+    for row in 0..SKETCH_DEPTH {
+        let counter = &mut sketch.counters[counter_index(addr, row)];
+        *counter = counter.saturating_add(1);
+        estimate = estimate.min(*counter);
+    }
+
+    let mut lowest = 0;
+    for (i, candidate) in sketch.candidates.iter().enumerate() {
+        if candidate.addr == addr && candidate.packets != 0 {
+            sketch.candidates[i].packets = estimate;
+            return;
+        }
+        if candidate.packets < sketch.candidates[lowest].packets {
+            lowest = i;
+        }
+    }
+    if estimate > sketch.candidates[lowest].packets {
+        sketch.candidates[lowest] = Candidate {
+            addr,
+            packets: estimate,
+        };
+    }
+}
+
+/// Estimates the packets of the address counted in all the sketches, e.g. those of
+/// each CPU.
+pub fn estimate(sketches: &[HeavyHitterSketch], addr: u32) -> u64 {
+    (0..SKETCH_DEPTH)
+        .map(|row| {
+            let index = counter_index(addr, row);
+            sketches
+                .iter()
+                .map(|sketch| sketch.counters[index] as u64)
+                .sum()
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_never_undercount() {
+        let mut sketch = HeavyHitterSketch::EMPTY;
+        for addr in 0..5000 {
+            for _ in 0..addr % 7 {
+                count(&mut sketch, addr);
+            }
+        }
+
+        for addr in 0..5000 {
+            assert!(estimate(&[sketch], addr) >= (addr % 7) as u64);
+        }
+    }
+
+    #[test]
+    fn heavy_hitters_become_candidates() {
+        let mut sketch = HeavyHitterSketch::EMPTY;
+        for round in 0..100 {
+            for addr in 0..1000 {
+                count(&mut sketch, addr);
+            }
+            for _ in 0..round {
+                count(&mut sketch, 0x0a000001);
+                count(&mut sketch, 0x0a000002);
+            }
+        }
+
+        let is_candidate = |addr| {
+            sketch
+                .candidates
+                .iter()
+                .any(|candidate| candidate.addr == addr && candidate.packets != 0)
+        };
+        assert!(is_candidate(0x0a000001));
+        assert!(is_candidate(0x0a000002));
+    }
+
+    #[test]
+    fn estimate_sums_sketches() {
+        let mut sketches = [HeavyHitterSketch::EMPTY; 2];
+        for _ in 0..3 {
+            count(&mut sketches[0], 7);
+        }
+        count(&mut sketches[1], 7);
+
+        assert_eq!(estimate(&sketches, 7), 4);
+        assert_eq!(estimate(&sketches[1..], 7), 1);
+    }
+}
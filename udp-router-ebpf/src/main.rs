@@ -1,20 +1,65 @@
 #![no_std]
 #![no_main]
 
-use core::mem;
+use core::{
+    mem,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use aya_ebpf::{
-    bindings::xdp_action::{self, XDP_PASS},
+    bindings::{
+        xdp_action::{self, XDP_DROP, XDP_PASS, XDP_TX},
+        BPF_F_NO_PREALLOC,
+    },
+    helpers::{bpf_ktime_get_ns, bpf_xdp_adjust_head, bpf_xdp_adjust_tail},
     macros::{map, xdp},
-    maps::{Array, PerCpuArray},
+    maps::{
+        lpm_trie::Key, Array, DevMap, HashMap, LpmTrie, LruHashMap, LruPerCpuHashMap, PerCpuArray,
+        PerCpuHashMap,
+    },
     programs::XdpContext,
 };
 use aya_log_ebpf::info;
 use network_types::{
     eth::{EthHdr, EtherType},
+    icmp::IcmpHdr,
     ip::{IpProto, Ipv4Hdr},
     udp::UdpHdr,
 };
+use udp_router_common::{
+    checksum::{fold_checksum, update_udp_checksum, UDP_NO_CHECKSUM},
+    client_token, cookie, flow_key, hyperloglog, maglev,
+    siphash::header_tag,
+    sketch, BackendRemap, BlocklistEntry, ClientRegisters, ClientTraffic, FlowAffinity,
+    HeavyHitterSketch, RateLimit, SipKey, TokenBucket, TrafficCounter, AMPLIFICATION_HALF_LIFE_NS,
+    BACKEND_DRAINING, BACKEND_INTERFACE, BACKEND_UP, CHALLENGES_REQUIRED, CHALLENGE_LEN,
+    CHALLENGE_MAGIC, CLIENT_INTERFACE, CLIENT_TOKENS_ENABLED, CLIENT_TOKEN_LEN,
+    CONFIG_CHALLENGE_POLICY, CONFIG_CLIENT_BURST, CONFIG_CLIENT_RATE_LIMIT,
+    CONFIG_CLIENT_TOKEN_POLICY, CONFIG_FLOW_IDLE_TIMEOUT, CONFIG_FRAGMENT_POLICY,
+    CONFIG_HEADER_AUTH_POLICY, CONFIG_IP_OPTIONS_POLICY, CONFIG_MAX_AMPLIFICATION,
+    CONFIG_MIN_ROUTING_HEADER_LEN, CONFIG_SEEN_CLIENT_WINDOW, CONFIG_TRAFFIC_CLIENT_PREFIX_LEN,
+    CONFIG_TTL_EXPIRED_POLICY, CONFIG_UDP_ZERO_CHECKSUM_POLICY, COOKIE_LEN, COOKIE_LIFETIME_SECS,
+    COUNTER_AMPLIFICATION_DROPPED, COUNTER_BACKEND_OVERLOAD_DROPPED, COUNTER_BACKEND_REMAPPED,
+    COUNTER_BLOCKLIST_DROPPED, COUNTER_CHALLENGES_SENT, COUNTER_CLIENT_TOKEN_DROPPED,
+    COUNTER_COOKIES_VERIFIED, COUNTER_COOKIE_DROPPED, COUNTER_FIRST_FRAGMENTS_FORWARDED,
+    COUNTER_FRAGMENTS_DROPPED, COUNTER_FRAGMENTS_PASSED, COUNTER_HEADER_AUTH_DROPPED,
+    COUNTER_ICMP_TIME_EXCEEDED_SENT, COUNTER_IP_LENGTH_DROPPED, COUNTER_IP_OPTIONS_DROPPED,
+    COUNTER_NON_UNICAST_DROPPED, COUNTER_RATE_LIMITED_DROPPED, COUNTER_SERVICE_UNAVAILABLE_DROPPED,
+    COUNTER_SHORT_ROUTING_HEADER_DROPPED, COUNTER_TARGET_BROADCAST_DROPPED,
+    COUNTER_TARGET_LINK_LOCAL_DROPPED, COUNTER_TARGET_LOCAL_NETWORK_DROPPED,
+    COUNTER_TARGET_LOOPBACK_DROPPED, COUNTER_TARGET_MULTICAST_DROPPED,
+    COUNTER_TARGET_ROUTER_DROPPED, COUNTER_TARGET_UNSPECIFIED_DROPPED, COUNTER_TTL_EXPIRED_DROPPED,
+    COUNTER_UDP_CHECKSUM_ADDED, COUNTER_UDP_LENGTH_DROPPED, COUNTER_UNAVAILABLE_BACKEND_SKIPPED,
+    COUNTER_UNSEEN_CLIENT_DROPPED, DEFAULT_FLOW_IDLE_TIMEOUT_NS, FRAGMENTS_FORWARD_FIRST,
+    FRAGMENTS_PASS, HEADER_AUTH_REQUIRED, HEADER_TAG_LEN, HEALTH_REPLY, IP_OPTIONS_DROP,
+    KEY_CURRENT, KEY_PREVIOUS, MAGLEV_TABLE_SIZE, MAX_AMPLIFICATION_CLIENTS, MAX_BACKEND_REMAPS,
+    MAX_BLOCKLIST_ENTRIES, MAX_HEALTH_CHECKED_BACKENDS, MAX_PREFIX_RATE_LIMITS,
+    MAX_RATE_LIMITED_BACKENDS, MAX_RATE_LIMITED_CLIENTS, MAX_SEEN_CLIENTS, MAX_SERVICES,
+    MAX_SERVICE_BACKEND_COUNTERS, MAX_SERVICE_FLOWS, MAX_TRAFFIC_BACKENDS,
+    MAX_TRAFFIC_CLIENT_PREFIXES, NUM_CONFIG_ENTRIES, NUM_COUNTERS, NUM_INTERFACES, NUM_KEYS,
+    NUM_SKETCHES, ROUTING_HEADER_LEN, SERVICE_MASK, SERVICE_NET, SKETCH_SOURCES, SKETCH_TARGETS,
+    TOKEN_SCALE, TTL_EXPIRED_ICMP, UDP_ZERO_CHECKSUM_CALCULATE,
+};
 
 // Macro for reading map constants
 macro_rules! read {
@@ -46,6 +91,11 @@ macro_rules! read {
 //
 // Note that backend & local network can overlap, i.e. backend can be whole VPC. The
 // local network takes precedence.
+//
+// The router can optionally be attached to two interfaces, one facing the clients
+// and one facing the backends. In that mode packets are redirected to the interface
+// facing their destination instead of being sent back out of the ingress interface,
+// and each side has its own gateway and source MAC address.
 
 // Any packets from this network are passed through XDP filter
 #[map]
@@ -55,9 +105,152 @@ static mut LOCAL_NET_AND_MASK: Array<u64> = Array::with_max_entries(1, 0);
 #[map]
 static mut BACKEND_NET_AND_MASK: Array<u64> = Array::with_max_entries(1, 0);
 
-// MAC address of default gateway of router.
+// MAC address of default gateway of router, indexed by interface side.
+#[map]
+static mut GATEWAY_MAC_ADDRESS: Array<u64> = Array::with_max_entries(NUM_INTERFACES, 0);
+
+// Source MAC address of forwarded packets, indexed by interface side. The controller
+// fills this in with the MAC address of each interface the program is attached to.
+#[map]
+static mut SOURCE_MAC_ADDRESS: Array<u64> = Array::with_max_entries(NUM_INTERFACES, 0);
+
+// Interfaces to redirect packets to in two-interface mode, indexed by interface side.
+// If the backend side is not set, packets are sent back out of the ingress interface.
+#[map]
+static mut REDIRECT_INTERFACES: DevMap = DevMap::with_max_entries(NUM_INTERFACES, 0);
+
+// Client prefixes whose traffic is dropped, keyed by address in network byte order
+#[map]
+static mut BLOCKLIST: LpmTrie<u32, BlocklistEntry> =
+    LpmTrie::with_max_entries(MAX_BLOCKLIST_ENTRIES, BPF_F_NO_PREALLOC);
+
+// Rate limits of client prefixes, keyed by address in network byte order
+#[map]
+static mut CLIENT_RATE_LIMITS: LpmTrie<u32, RateLimit> =
+    LpmTrie::with_max_entries(MAX_PREFIX_RATE_LIMITS, BPF_F_NO_PREALLOC);
+
+// Token buckets of clients, keyed by address in host byte order
 #[map]
-static mut GATEWAY_MAC_ADDRESS: Array<u64> = Array::with_max_entries(1, 0);
+static mut CLIENT_BUCKETS: LruHashMap<u32, TokenBucket> =
+    LruHashMap::with_max_entries(MAX_RATE_LIMITED_CLIENTS, 0);
+
+// Rate limits of backend prefixes, keyed by address in network byte order
+#[map]
+static mut BACKEND_RATE_LIMITS: LpmTrie<u32, RateLimit> =
+    LpmTrie::with_max_entries(MAX_PREFIX_RATE_LIMITS, BPF_F_NO_PREALLOC);
+
+// Token buckets of backends, keyed by address in host byte order
+#[map]
+static mut BACKEND_BUCKETS: LruHashMap<u32, TokenBucket> =
+    LruHashMap::with_max_entries(MAX_RATE_LIMITED_BACKENDS, 0);
+
+// Time each client was last seen at in nanoseconds of CLOCK_MONOTONIC, keyed by address
+// in host byte order
+#[map]
+static mut SEEN_CLIENTS: LruHashMap<u32, u64> = LruHashMap::with_max_entries(MAX_SEEN_CLIENTS, 0);
+
+// Bytes of requests from and responses to each client, keyed by address in host byte
+// order
+#[map]
+static mut CLIENT_TRAFFIC: LruHashMap<u32, ClientTraffic> =
+    LruHashMap::with_max_entries(MAX_AMPLIFICATION_CLIENTS, 0);
+
+// Maglev lookup tables of services, MAGLEV_TABLE_SIZE entries per service ID
+#[map]
+static mut SERVICE_TABLES: Array<u32> =
+    Array::with_max_entries(MAX_SERVICES * MAGLEV_TABLE_SIZE as u32, 0);
+
+// Packets forwarded to each backend through a service, keyed by address in host byte
+// order
+#[map]
+static mut SERVICE_BACKEND_PACKETS: LruPerCpuHashMap<u32, u64> =
+    LruPerCpuHashMap::with_max_entries(MAX_SERVICE_BACKEND_COUNTERS, 0);
+
+// States of backends, keyed by address in host byte order. Backends without an
+// entry are up.
+#[map]
+static mut BACKEND_STATES: HashMap<u32, u32> =
+    HashMap::with_max_entries(MAX_HEALTH_CHECKED_BACKENDS, 0);
+
+// Backends client flows of services were last sent to, keyed by flow_key()
+#[map]
+static mut SERVICE_FLOWS: LruHashMap<u64, FlowAffinity> =
+    LruHashMap::with_max_entries(MAX_SERVICE_FLOWS, 0);
+
+// New addresses of backends that moved, keyed by old address in host byte order
+#[map]
+static mut BACKEND_REMAPS: HashMap<u32, BackendRemap> =
+    HashMap::with_max_entries(MAX_BACKEND_REMAPS, 0);
+
+// Traffic forwarded to and from each backend, keyed by address in host byte order
+#[map]
+static mut BACKEND_TRAFFIC: PerCpuHashMap<u32, TrafficCounter> =
+    PerCpuHashMap::with_max_entries(MAX_TRAFFIC_BACKENDS, 0);
+
+// Traffic forwarded from and to each client prefix, keyed by network address in host
+// byte order
+#[map]
+static mut CLIENT_PREFIX_TRAFFIC: PerCpuHashMap<u32, TrafficCounter> =
+    PerCpuHashMap::with_max_entries(MAX_TRAFFIC_CLIENT_PREFIXES, 0);
+
+// Count-min sketches of clients and backends, indexed by SKETCH_SOURCES and
+// SKETCH_TARGETS
+#[map]
+static mut HEAVY_HITTERS: PerCpuArray<HeavyHitterSketch> =
+    PerCpuArray::with_max_entries(NUM_SKETCHES, 0);
+
+// HyperLogLog registers of the clients packets were forwarded from or to
+#[map]
+static mut CLIENT_REGISTERS: PerCpuArray<ClientRegisters> = PerCpuArray::with_max_entries(1, 0);
+
+// Keys of routing header tags, indexed by KEY_CURRENT and KEY_PREVIOUS
+#[map]
+static mut HEADER_KEYS: Array<SipKey> = Array::with_max_entries(NUM_KEYS, 0);
+
+// Keys of client tokens, indexed by KEY_CURRENT and KEY_PREVIOUS
+#[map]
+static mut CLIENT_TOKEN_KEYS: Array<SipKey> = Array::with_max_entries(NUM_KEYS, 0);
+
+// Keys of challenge cookies, indexed by KEY_CURRENT and KEY_PREVIOUS
+#[map]
+static mut COOKIE_KEYS: Array<SipKey> = Array::with_max_entries(NUM_KEYS, 0);
+
+// Remaining settings, indexed by the CONFIG_* constants in udp-router-common
+#[map]
+static mut CONFIG: Array<u64> = Array::with_max_entries(NUM_CONFIG_ENTRIES, 0);
+
+// Maximum length of an IPv4 header including options
+const MAX_IPV4_HDR_LEN: usize = 60;
+
+// More fragments flag and fragment offset in the IPv4 header
+const IPV4_MORE_FRAGMENTS: u16 = 0x2000;
+const IPV4_FRAGMENT_OFFSET_MASK: u16 = 0x1fff;
+
+// Maximum length of UDP datagrams the router calculates a full checksum for
+const MAX_UDP_CHECKSUM_LEN: usize = 1500;
+
+// ICMP Time Exceeded messages carry the original IPv4 header and the first 8 bytes of
+// the original datagram (RFC 792)
+const ICMP_TIME_EXCEEDED: u8 = 11;
+const ICMP_ORIGINAL_DATAGRAM_LEN: usize = 8;
+const MAX_ICMP_TIME_EXCEEDED_LEN: usize =
+    IcmpHdr::LEN + MAX_IPV4_HDR_LEN + ICMP_ORIGINAL_DATAGRAM_LEN;
+
+// Maximum length of the headers moved when resizing the routing header, including the
+// routing header itself
+const MAX_MOVED_HEADERS_LEN: usize =
+    EthHdr::LEN + MAX_IPV4_HDR_LEN + UdpHdr::LEN + ROUTING_HEADER_LEN;
+
+// Maximum number of lookup table entries tried for a service packet whose backend is
+// down. Entries following each other belong to unrelated backends.
+const MAX_SERVICE_LOOKUPS: u32 = 16;
+
+// TTL of packets originating from the router
+const DEFAULT_TTL: u8 = 64;
+
+// Maximum time token buckets are refilled for at once, keeping the number of tokens
+// from overflowing
+const MAX_REFILL_NS: u64 = 60_000_000_000;
 
 //
 // Counters
@@ -70,6 +263,12 @@ static mut CLIENT_TO_SERVER_PACKETS: PerCpuArray<u64> = PerCpuArray::with_max_en
 #[map]
 static mut SERVER_TO_CLIENT_PACKETS: PerCpuArray<u64> = PerCpuArray::with_max_entries(1, 0);
 
+// Remaining counters, indexed by the COUNTER_* constants in udp-router-common. Kept in
+// a single map so new counters don't count against the verifier's limit on maps used
+// by a program.
+#[map]
+static mut COUNTERS: PerCpuArray<u64> = PerCpuArray::with_max_entries(NUM_COUNTERS, 0);
+
 //
 // Router implementation
 //
@@ -101,7 +300,866 @@ fn try_udp_router(ctx: XdpContext) -> Result<u32, ()> {
         return Ok(XDP_PASS);
     }
 
-    try_forward_packet(&ctx)
+    if is_blocked(source_ip) {
+        increment_counter(&raw mut COUNTERS, COUNTER_BLOCKLIST_DROPPED);
+        return Ok(XDP_DROP);
+    }
+
+    // Clients are counted before any other check, so that floods of malformed packets
+    // show up among the heavy hitters as well
+    if !is_from_backend_server(source_ip) {
+        count_heavy_hitter(SKETCH_SOURCES, source_ip);
+    }
+
+    // Header length in bytes. Malformed headers are left for the kernel to discard.
+    let ip_header_len = unsafe { (*ipv4hdr).ihl() as usize } * 4;
+    if ip_header_len < Ipv4Hdr::LEN {
+        return Ok(XDP_PASS);
+    }
+    let ip_options_policy = read!(CONFIG, CONFIG_IP_OPTIONS_POLICY);
+    if ip_header_len > Ipv4Hdr::LEN && ip_options_policy == IP_OPTIONS_DROP {
+        increment_counter(&raw mut COUNTERS, COUNTER_IP_OPTIONS_DROPPED);
+        return Ok(XDP_DROP);
+    }
+
+    // Only the first fragment of a datagram carries the UDP and routing headers
+    let frag_off = u16::from_be(unsafe { (*ipv4hdr).frag_off });
+    let is_fragment = frag_off & (IPV4_MORE_FRAGMENTS | IPV4_FRAGMENT_OFFSET_MASK) != 0;
+    if is_fragment {
+        let fragment_policy = read!(CONFIG, CONFIG_FRAGMENT_POLICY);
+        if fragment_policy == FRAGMENTS_PASS {
+            increment_counter(&raw mut COUNTERS, COUNTER_FRAGMENTS_PASSED);
+            return Ok(XDP_PASS);
+        }
+        let first_fragment = frag_off & IPV4_FRAGMENT_OFFSET_MASK == 0;
+        if fragment_policy != FRAGMENTS_FORWARD_FIRST || !first_fragment {
+            increment_counter(&raw mut COUNTERS, COUNTER_FRAGMENTS_DROPPED);
+            return Ok(XDP_DROP);
+        }
+    }
+
+    // Reject packets whose length fields disagree with each other or the frame
+    if let Some(counter) = check_lengths(&ctx, ip_header_len, is_fragment)? {
+        increment_counter(&raw mut COUNTERS, counter);
+        return Ok(XDP_DROP);
+    }
+
+    // Broadcast and multicast frames are never forwarded
+    if !is_unicast_frame(&ctx)? {
+        increment_counter(&raw mut COUNTERS, COUNTER_NON_UNICAST_DROPPED);
+        return Ok(XDP_DROP);
+    }
+
+    // Answers to health check probes are for the controller on the router itself
+    let from_backend = is_from_backend_server(source_ip);
+    if from_backend && routing_target(&ctx, ip_header_len)? == HEALTH_REPLY {
+        return Ok(XDP_PASS);
+    }
+
+    // Responses carry the token of their client instead of its address. Restoring the
+    // address moves the IPv4 header.
+    let client_tokens = read!(CONFIG, CONFIG_CLIENT_TOKEN_POLICY) == CLIENT_TOKENS_ENABLED;
+    if from_backend && client_tokens && !restore_client_address(&ctx, ip_header_len)? {
+        increment_counter(&raw mut COUNTERS, COUNTER_CLIENT_TOKEN_DROPPED);
+        return Ok(XDP_DROP);
+    }
+    let ipv4hdr: *mut Ipv4Hdr = ptr_at_mut(&ctx, EthHdr::LEN)?;
+
+    // Never forward to the router itself or to addresses that are not a single host
+    if let Some(counter) = check_target(&ctx, ip_header_len)? {
+        increment_counter(&raw mut COUNTERS, counter);
+        return Ok(XDP_DROP);
+    }
+
+    // Responses are only forwarded to clients that recently sent packets themselves,
+    // and not in much larger volume than the clients sent
+    let seen_client_window = read!(CONFIG, CONFIG_SEEN_CLIENT_WINDOW);
+    let max_amplification = read!(CONFIG, CONFIG_MAX_AMPLIFICATION);
+    let ip_len = u16::from_be(unsafe { (*ipv4hdr).tot_len }) as u64;
+    if from_backend {
+        let client_ip = routing_target(&ctx, ip_header_len)?;
+        if seen_client_window != 0 && !is_seen_client(client_ip, seen_client_window) {
+            increment_counter(&raw mut COUNTERS, COUNTER_UNSEEN_CLIENT_DROPPED);
+            return Ok(XDP_DROP);
+        }
+        if max_amplification != 0 && !count_response(client_ip, ip_len, max_amplification) {
+            increment_counter(&raw mut COUNTERS, COUNTER_AMPLIFICATION_DROPPED);
+            return Ok(XDP_DROP);
+        }
+    }
+
+    // Pick the interface facing the destination of the forwarded packet
+    let two_interface_mode = is_two_interface_mode();
+    let egress = if from_backend || !two_interface_mode {
+        CLIENT_INTERFACE
+    } else {
+        BACKEND_INTERFACE
+    };
+
+    // Only clients holding a header key can make the router forward packets. Checked
+    // before rate limiting, so spoofed packets do not use up the tokens of the client.
+    let tagged = !from_backend && read!(CONFIG, CONFIG_HEADER_AUTH_POLICY) == HEADER_AUTH_REQUIRED;
+    if tagged && !is_header_authentic(&ctx, ip_header_len)? {
+        increment_counter(&raw mut COUNTERS, COUNTER_HEADER_AUTH_DROPPED);
+        return Ok(XDP_DROP);
+    }
+
+    // Clients must prove they receive packets sent to their address, so that packets
+    // with spoofed source addresses only cause challenges to the spoofed address. The
+    // packet must be long enough to hold a cookie, or the challenge would amplify it.
+    let challenged = !from_backend && read!(CONFIG, CONFIG_CHALLENGE_POLICY) == CHALLENGES_REQUIRED;
+    if challenged {
+        let cookie_offset = match tagged {
+            true => ROUTING_HEADER_LEN + HEADER_TAG_LEN,
+            false => ROUTING_HEADER_LEN,
+        };
+        if (ip_len as usize) < ip_header_len + UdpHdr::LEN + cookie_offset + COOKIE_LEN {
+            increment_counter(&raw mut COUNTERS, COUNTER_COOKIE_DROPPED);
+            return Ok(XDP_DROP);
+        }
+
+        let now = (unsafe { bpf_ktime_get_ns() } / 1_000_000_000) as u32;
+        if !is_cookie_valid(&ctx, ip_header_len, cookie_offset, source_ip, now)? {
+            let Some(cookie) = make_cookie(source_ip, now) else {
+                increment_counter(&raw mut COUNTERS, COUNTER_COOKIE_DROPPED);
+                return Ok(XDP_DROP);
+            };
+            let action = send_challenge(&ctx, ip_header_len, &cookie)?;
+            increment_counter(&raw mut COUNTERS, COUNTER_CHALLENGES_SENT);
+            return Ok(action);
+        }
+        increment_counter(&raw mut COUNTERS, COUNTER_COOKIES_VERIFIED);
+    }
+
+    // Backends are protected from clients sending faster than their rate limit
+    if !from_backend && is_rate_limited(source_ip) {
+        increment_counter(&raw mut COUNTERS, COUNTER_RATE_LIMITED_DROPPED);
+        return Ok(XDP_DROP);
+    }
+
+    // Packets must not be forwarded forever in case of routing loops
+    if unsafe { (*ipv4hdr).ttl } <= 1 {
+        if read!(CONFIG, CONFIG_TTL_EXPIRED_POLICY) != TTL_EXPIRED_ICMP {
+            increment_counter(&raw mut COUNTERS, COUNTER_TTL_EXPIRED_DROPPED);
+            return Ok(XDP_DROP);
+        }
+
+        let ingress = if from_backend && two_interface_mode {
+            BACKEND_INTERFACE
+        } else {
+            CLIENT_INTERFACE
+        };
+        let action = send_time_exceeded(&ctx, ip_header_len, ingress)?;
+        increment_counter(&raw mut COUNTERS, COUNTER_ICMP_TIME_EXCEEDED_SENT);
+        return Ok(action);
+    }
+
+    // Clients are remembered once their packets are known to be forwarded
+    if !from_backend && seen_client_window != 0 {
+        remember_client(source_ip);
+    }
+    if !from_backend && max_amplification != 0 {
+        count_request(source_ip, ip_len);
+    }
+
+    // Backends receive the routing header without the tag
+    if tagged {
+        cut_payload::<HEADER_TAG_LEN>(&ctx, ip_header_len, ROUTING_HEADER_LEN)?;
+    }
+    if challenged {
+        cut_payload::<COOKIE_LEN>(&ctx, ip_header_len, ROUTING_HEADER_LEN)?;
+    }
+
+    // Backends see clients by their token, which takes more room than an address
+    let client_token = if !from_backend && client_tokens {
+        let Some(token) = encode_client_token(source_ip) else {
+            increment_counter(&raw mut COUNTERS, COUNTER_CLIENT_TOKEN_DROPPED);
+            return Ok(XDP_DROP);
+        };
+        insert_payload::<{ CLIENT_TOKEN_LEN - ROUTING_HEADER_LEN }>(
+            &ctx,
+            ip_header_len,
+            ROUTING_HEADER_LEN,
+        )?;
+        Some(token)
+    } else {
+        None
+    };
+
+    let action = try_forward_packet(&ctx, ip_header_len, egress, from_backend, client_token)?;
+
+    if is_fragment {
+        increment_counter(&raw mut COUNTERS, COUNTER_FIRST_FRAGMENTS_FORWARDED);
+    }
+    increment_counter(&raw mut TOTAL_PACKETS, 0);
+    if from_backend {
+        increment_counter(&raw mut SERVER_TO_CLIENT_PACKETS, 0);
+    } else {
+        increment_counter(&raw mut CLIENT_TO_SERVER_PACKETS, 0);
+    }
+
+    Ok(action)
+}
+
+#[inline(always)]
+fn is_two_interface_mode() -> bool {
+    unsafe { REDIRECT_INTERFACES.get(BACKEND_INTERFACE).is_some() }
+}
+
+fn is_unicast_frame(ctx: &XdpContext) -> Result<bool, ()> {
+    let ethhdr: *const EthHdr = ptr_at_mut(ctx, 0)?;
+
+    // The I/G bit of the destination address is set for group addresses
+    Ok(unsafe { (*ethhdr).dst_addr[0] } & 0x01 == 0)
+}
+
+// Returns the counter to increment if the packet must be dropped due to its length.
+// Frames may be longer than the IPv4 total length, as short frames are padded. The
+// UDP length of a fragment covers the whole datagram. Only first fragments are checked,
+// as the others have no UDP header.
+fn check_lengths(
+    ctx: &XdpContext,
+    ip_header_len: usize,
+    is_fragment: bool,
+) -> Result<Option<u32>, ()> {
+    let ipv4hdr: *const Ipv4Hdr = ptr_at_mut(ctx, EthHdr::LEN)?;
+    let udphdr: *const UdpHdr = ptr_at_mut(ctx, EthHdr::LEN + ip_header_len)?;
+
+    let frame_len = ctx.data_end() - ctx.data();
+    let ip_len = u16::from_be(unsafe { (*ipv4hdr).tot_len }) as usize;
+    if ip_len < ip_header_len + UdpHdr::LEN || EthHdr::LEN + ip_len > frame_len {
+        return Ok(Some(COUNTER_IP_LENGTH_DROPPED));
+    }
+
+    let ip_payload_len = ip_len - ip_header_len;
+    let udp_len = u16::from_be(unsafe { (*udphdr).len }) as usize;
+    if udp_len < ip_payload_len || (!is_fragment && udp_len != ip_payload_len) {
+        return Ok(Some(COUNTER_UDP_LENGTH_DROPPED));
+    }
+
+    let min_routing_header_len = match read!(CONFIG, CONFIG_MIN_ROUTING_HEADER_LEN) as usize {
+        len if len < ROUTING_HEADER_LEN => ROUTING_HEADER_LEN,
+        len => len,
+    };
+    if ip_payload_len < UdpHdr::LEN + min_routing_header_len {
+        return Ok(Some(COUNTER_SHORT_ROUTING_HEADER_DROPPED));
+    }
+
+    Ok(None)
+}
+
+// Returns the counter to increment if the packet must be dropped due to the target
+// address in its routing header. The router's own address is usually inside the local
+// network, so it is checked first.
+fn check_target(ctx: &XdpContext, ip_header_len: usize) -> Result<Option<u32>, ()> {
+    let ipv4hdr: *const Ipv4Hdr = ptr_at_mut(ctx, EthHdr::LEN)?;
+    let payload: *const u32 = ptr_at_mut(ctx, EthHdr::LEN + ip_header_len + UdpHdr::LEN)?;
+
+    let router_ip = u32::from_be(unsafe { (*ipv4hdr).dst_addr });
+    let target_ip = u32::from_be(unsafe { *payload });
+
+    let counter = if target_ip >> 24 == 0 {
+        COUNTER_TARGET_UNSPECIFIED_DROPPED
+    } else if target_ip >> 24 == 127 {
+        COUNTER_TARGET_LOOPBACK_DROPPED
+    } else if target_ip >> 28 == 0xe {
+        COUNTER_TARGET_MULTICAST_DROPPED
+    } else if target_ip == 0xffffffff {
+        COUNTER_TARGET_BROADCAST_DROPPED
+    } else if is_link_local_ip(target_ip) {
+        COUNTER_TARGET_LINK_LOCAL_DROPPED
+    } else if target_ip == router_ip {
+        COUNTER_TARGET_ROUTER_DROPPED
+    } else if is_from_local_network(target_ip) {
+        COUNTER_TARGET_LOCAL_NETWORK_DROPPED
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(counter))
+}
+
+// Returns true if the routing header is followed by a tag made with an active key over
+// the header and the source address of the packet.
+fn is_header_authentic(ctx: &XdpContext, ip_header_len: usize) -> Result<bool, ()> {
+    let ipv4hdr: *const Ipv4Hdr = ptr_at_mut(ctx, EthHdr::LEN)?;
+
+    // The frame may be padded beyond the IPv4 total length
+    let ip_len = u16::from_be(unsafe { (*ipv4hdr).tot_len }) as usize;
+    if ip_len < ip_header_len + UdpHdr::LEN + ROUTING_HEADER_LEN + HEADER_TAG_LEN {
+        return Ok(false);
+    }
+
+    let payload_offset = EthHdr::LEN + ip_header_len + UdpHdr::LEN;
+    let payload: *const u32 = ptr_at_mut(ctx, payload_offset)?;
+    let tag: *const [u8; HEADER_TAG_LEN] = ptr_at_mut(ctx, payload_offset + ROUTING_HEADER_LEN)?;
+
+    let source_ip = u32::from_be(unsafe { (*ipv4hdr).src_addr });
+    let target_ip = u32::from_be(unsafe { *payload });
+    let tag = u64::from_le_bytes(unsafe { *tag });
+
+    for index in [KEY_CURRENT, KEY_PREVIOUS] {
+        let Some(key) = (unsafe { HEADER_KEYS.get(index) }) else {
+            continue;
+        };
+        if key.active != 0 && header_tag(key.k0, key.k1, target_ip, source_ip) == tag {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+// Returns true if the payload holds a cookie at `offset`, made for the source address of
+// the packet with an active key within the cookie lifetime.
+fn is_cookie_valid(
+    ctx: &XdpContext,
+    ip_header_len: usize,
+    offset: usize,
+    source_ip: u32,
+    now: u32,
+) -> Result<bool, ()> {
+    let cookie_offset = EthHdr::LEN + ip_header_len + UdpHdr::LEN + offset;
+    let cookie: *const [u8; COOKIE_LEN] = ptr_at_mut(ctx, cookie_offset)?;
+    let cookie = unsafe { *cookie };
+
+    let timestamp = cookie::timestamp(&cookie);
+    if timestamp > now || (now - timestamp) as u64 > COOKIE_LIFETIME_SECS {
+        return Ok(false);
+    }
+
+    for index in [KEY_CURRENT, KEY_PREVIOUS] {
+        let Some(key) = (unsafe { COOKIE_KEYS.get(index) }) else {
+            continue;
+        };
+        if key.active != 0 && cookie::make(key.k0, key.k1, source_ip, timestamp) == cookie {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+// Returns the cookie of the client made with the current key, or None if there is none.
+fn make_cookie(source_ip: u32, now: u32) -> Option<[u8; COOKIE_LEN]> {
+    let key = unsafe { COOKIE_KEYS.get(KEY_CURRENT) }?;
+    if key.active == 0 {
+        return None;
+    }
+
+    Some(cookie::make(key.k0, key.k1, source_ip, now))
+}
+
+// Returns the token of the client made with the current key, or None if there is none.
+fn encode_client_token(client_ip: u32) -> Option<u64> {
+    let key = unsafe { CLIENT_TOKEN_KEYS.get(KEY_CURRENT) }?;
+    if key.active == 0 {
+        return None;
+    }
+
+    Some(client_token::encode(key.k0, key.k1, client_ip))
+}
+
+// Returns the address of the client a token was made for with an active key.
+fn decode_client_token(token: u64) -> Option<u32> {
+    for index in [KEY_CURRENT, KEY_PREVIOUS] {
+        let Some(key) = (unsafe { CLIENT_TOKEN_KEYS.get(index) }) else {
+            continue;
+        };
+        if key.active == 0 {
+            continue;
+        }
+        if let Some(client_ip) = client_token::decode(key.k0, key.k1, token) {
+            return Some(client_ip);
+        }
+    }
+
+    None
+}
+
+// Replaces the client token in the routing header of a response by the address of the
+// client. Returns false if the token was not made with an active key.
+fn restore_client_address(ctx: &XdpContext, ip_header_len: usize) -> Result<bool, ()> {
+    let ipv4hdr: *const Ipv4Hdr = ptr_at_mut(ctx, EthHdr::LEN)?;
+    let udphdr: *mut UdpHdr = ptr_at_mut(ctx, EthHdr::LEN + ip_header_len)?;
+
+    // The frame may be padded beyond the IPv4 total length
+    let ip_len = u16::from_be(unsafe { (*ipv4hdr).tot_len }) as usize;
+    if ip_len < ip_header_len + UdpHdr::LEN + CLIENT_TOKEN_LEN {
+        return Ok(false);
+    }
+
+    let payload_offset = EthHdr::LEN + ip_header_len + UdpHdr::LEN;
+    let token: *const [u8; CLIENT_TOKEN_LEN] = ptr_at_mut(ctx, payload_offset)?;
+    let Some(client_ip) = decode_client_token(u64::from_be_bytes(unsafe { *token })) else {
+        return Ok(false);
+    };
+
+    // The address takes the place of the end of the token, and the rest is cut
+    let cut_len = CLIENT_TOKEN_LEN - ROUTING_HEADER_LEN;
+    let address: *mut [u8; ROUTING_HEADER_LEN] = ptr_at_mut(ctx, payload_offset + cut_len)?;
+    unsafe {
+        let mut udp_csum_ne = u16::from_be((*udphdr).check);
+        udp_csum_ne = update_udp_checksum_words(udp_csum_ne, &*address, &client_ip.to_be_bytes());
+        *address = client_ip.to_be_bytes();
+        (*udphdr).check = u16::to_be(udp_csum_ne);
+    }
+    cut_payload::<{ CLIENT_TOKEN_LEN - ROUTING_HEADER_LEN }>(ctx, ip_header_len, 0)?;
+
+    Ok(true)
+}
+
+// Removes N bytes at `offset` into the UDP payload by moving the headers in front of
+// them over them. `offset` and N must be even. The IPv4 checksum is recalculated when
+// forwarding the packet.
+fn cut_payload<const N: usize>(
+    ctx: &XdpContext,
+    ip_header_len: usize,
+    offset: usize,
+) -> Result<(), ()> {
+    let ipv4hdr: *mut Ipv4Hdr = ptr_at_mut(ctx, EthHdr::LEN)?;
+    let udphdr: *mut UdpHdr = ptr_at_mut(ctx, EthHdr::LEN + ip_header_len)?;
+    let cut_offset = EthHdr::LEN + ip_header_len + UdpHdr::LEN + offset;
+    let cut: *const [u8; N] = ptr_at_mut(ctx, cut_offset)?;
+
+    // Removing the bytes takes their words out of the checksum
+    unsafe {
+        let udp_csum_ne = update_udp_checksum_words(u16::from_be((*udphdr).check), &*cut, &[0; N]);
+        let udp_len = u16::from_be((*udphdr).len) - N as u16;
+        set_udp_len(ipv4hdr, udphdr, udp_csum_ne, udp_len);
+    }
+
+    // Move the headers back to front, as the source and destination overlap
+    for i in (0..MAX_MOVED_HEADERS_LEN / 2).rev() {
+        if 2 * i + 2 > cut_offset {
+            continue;
+        }
+        let src: *const [u8; 2] = ptr_at_mut(ctx, 2 * i)?;
+        let dst: *mut [u8; 2] = ptr_at_mut(ctx, 2 * i + N)?;
+        unsafe { *dst = *src };
+    }
+
+    if unsafe { bpf_xdp_adjust_head(ctx.ctx, N as i32) } != 0 {
+        return Err(());
+    }
+
+    Ok(())
+}
+
+// Inserts N zero bytes at `offset` into the UDP payload by moving the headers in front
+// of it. `offset` and N must be even. The IPv4 checksum is recalculated when forwarding
+// the packet.
+fn insert_payload<const N: usize>(
+    ctx: &XdpContext,
+    ip_header_len: usize,
+    offset: usize,
+) -> Result<(), ()> {
+    if unsafe { bpf_xdp_adjust_head(ctx.ctx, -(N as i32)) } != 0 {
+        return Err(());
+    }
+
+    // Move the headers front to back, as the source and destination overlap
+    let insert_offset = EthHdr::LEN + ip_header_len + UdpHdr::LEN + offset;
+    for i in 0..MAX_MOVED_HEADERS_LEN / 2 {
+        if 2 * i + 2 > insert_offset {
+            break;
+        }
+        let src: *const [u8; 2] = ptr_at_mut(ctx, 2 * i + N)?;
+        let dst: *mut [u8; 2] = ptr_at_mut(ctx, 2 * i)?;
+        unsafe { *dst = *src };
+    }
+
+    // Zero bytes leave the checksum as it is
+    let ipv4hdr: *mut Ipv4Hdr = ptr_at_mut(ctx, EthHdr::LEN)?;
+    let udphdr: *mut UdpHdr = ptr_at_mut(ctx, EthHdr::LEN + ip_header_len)?;
+    let inserted: *mut [u8; N] = ptr_at_mut(ctx, insert_offset)?;
+    unsafe {
+        *inserted = [0; N];
+        let udp_len = u16::from_be((*udphdr).len) + N as u16;
+        set_udp_len(ipv4hdr, udphdr, u16::from_be((*udphdr).check), udp_len);
+    }
+
+    Ok(())
+}
+
+// Sets the UDP length and the IPv4 total length along with it, and the UDP checksum
+// updated for the new length. The UDP length is in both the header and the pseudo
+// header.
+#[inline(always)]
+fn set_udp_len(ipv4hdr: *mut Ipv4Hdr, udphdr: *mut UdpHdr, udp_csum_ne: u16, udp_len: u16) {
+    unsafe {
+        let old_udp_len = u16::from_be((*udphdr).len);
+        let mut udp_csum_ne = update_udp_checksum(udp_csum_ne, old_udp_len, udp_len);
+        udp_csum_ne = update_udp_checksum(udp_csum_ne, old_udp_len, udp_len);
+        let ip_len =
+            u16::from_be((*ipv4hdr).tot_len).wrapping_add(udp_len.wrapping_sub(old_udp_len));
+
+        (*udphdr).len = u16::to_be(udp_len);
+        (*udphdr).check = u16::to_be(udp_csum_ne);
+        (*ipv4hdr).tot_len = u16::to_be(ip_len);
+    }
+}
+
+// Updates a UDP checksum for changing bytes at an even offset into the UDP header from
+// `old` to `new`, word by word.
+#[inline(always)]
+fn update_udp_checksum_words<const N: usize>(mut csum: u16, old: &[u8; N], new: &[u8; N]) -> u16 {
+    for i in 0..N / 2 {
+        let old_word = u16::from_be_bytes([old[2 * i], old[2 * i + 1]]);
+        let new_word = u16::from_be_bytes([new[2 * i], new[2 * i + 1]]);
+        csum = update_udp_checksum(csum, old_word, new_word);
+    }
+
+    csum
+}
+
+// Returns the target address in the routing header of the packet
+#[inline(always)]
+fn routing_target(ctx: &XdpContext, ip_header_len: usize) -> Result<u32, ()> {
+    let payload: *const u32 = ptr_at_mut(ctx, EthHdr::LEN + ip_header_len + UdpHdr::LEN)?;
+
+    Ok(u32::from_be(unsafe { *payload }))
+}
+
+// Returns true if the client sent a packet within the last `window` nanoseconds
+fn is_seen_client(client_ip: u32, window: u64) -> bool {
+    let Some(seen_at) = (unsafe { SEEN_CLIENTS.get(&client_ip) }) else {
+        return false;
+    };
+
+    unsafe { bpf_ktime_get_ns() }.saturating_sub(*seen_at) <= window
+}
+
+// Records the client as seen now
+#[inline(always)]
+fn remember_client(client_ip: u32) {
+    let now = unsafe { bpf_ktime_get_ns() };
+    match unsafe { SEEN_CLIENTS.get_ptr_mut(&client_ip) } {
+        Some(seen_at) => unsafe { *seen_at = now },
+        None => {
+            let _ = unsafe { SEEN_CLIENTS.insert(&client_ip, &now, 0) };
+        }
+    }
+}
+
+// Counts the bytes of a request from the client
+fn count_request(client_ip: u32, bytes: u64) {
+    let Some(traffic) = (unsafe { CLIENT_TRAFFIC.get_ptr_mut(&client_ip) }) else {
+        let traffic = ClientTraffic {
+            request_bytes: bytes,
+            response_bytes: 0,
+            decayed_at: unsafe { bpf_ktime_get_ns() },
+        };
+        let _ = unsafe { CLIENT_TRAFFIC.insert(&client_ip, &traffic, 0) };
+        return;
+    };
+
+    decay_client_traffic(traffic);
+    unsafe { (*traffic).request_bytes += bytes };
+}
+
+// Counts the bytes of a response to the client, returning false without counting them
+// if they would exceed `max_amplification` times the bytes of its requests. Counts are
+// shared by all CPUs and updated without synchronization, so the limit is approximate.
+fn count_response(client_ip: u32, bytes: u64, max_amplification: u64) -> bool {
+    let Some(traffic) = (unsafe { CLIENT_TRAFFIC.get_ptr_mut(&client_ip) }) else {
+        return false;
+    };
+
+    decay_client_traffic(traffic);
+    unsafe {
+        let response_bytes = (*traffic).response_bytes + bytes;
+        if response_bytes > (*traffic).request_bytes.saturating_mul(max_amplification) {
+            return false;
+        }
+        (*traffic).response_bytes = response_bytes;
+    }
+
+    true
+}
+
+// Halves the byte counts of a client once per half-life
+#[inline(always)]
+fn decay_client_traffic(traffic: *mut ClientTraffic) {
+    let now = unsafe { bpf_ktime_get_ns() };
+    unsafe {
+        if now.saturating_sub((*traffic).decayed_at) >= AMPLIFICATION_HALF_LIFE_NS {
+            (*traffic).request_bytes /= 2;
+            (*traffic).response_bytes /= 2;
+            (*traffic).decayed_at = now;
+        }
+    }
+}
+
+// Returns the backend of the service named by a routing header for the client, or None
+// if the service is unknown or has no backends
+fn service_backend(header: u32, client_ip: u32, client_port: u16) -> Option<u32> {
+    let service_id = header & !SERVICE_MASK;
+    if service_id >= MAX_SERVICES {
+        return None;
+    }
+
+    // Active clients stay with their backend while it is up or draining
+    let flow = flow_key(client_ip, client_port);
+    let now = unsafe { bpf_ktime_get_ns() };
+    let idle_timeout = match read!(CONFIG, CONFIG_FLOW_IDLE_TIMEOUT) {
+        0 => DEFAULT_FLOW_IDLE_TIMEOUT_NS,
+        timeout => timeout,
+    };
+    let affinity = unsafe { SERVICE_FLOWS.get_ptr_mut(&flow) }.filter(|&affinity| unsafe {
+        (*affinity).service_id as u32 == service_id
+            && ((*affinity).pinned != 0
+                || now.saturating_sub((*affinity).last_seen) <= idle_timeout)
+    });
+    if let Some(affinity) = affinity {
+        let backend_ip = unsafe { (*affinity).backend };
+        let state = backend_state(backend_ip);
+        if state == BACKEND_UP || state == BACKEND_DRAINING {
+            unsafe { (*affinity).last_seen = now };
+            return Some(backend_ip);
+        }
+    }
+
+    // Pinned flows return to their backend once it is back up
+    let backend_ip = lookup_service_backend(service_id, client_ip, client_port)?;
+    let pinned = affinity.is_some_and(|affinity| unsafe { (*affinity).pinned != 0 });
+    if !pinned {
+        let affinity = FlowAffinity {
+            backend: backend_ip,
+            service_id: service_id as u16,
+            pinned: 0,
+            last_seen: now,
+        };
+        let _ = unsafe { SERVICE_FLOWS.insert(&flow, &affinity, 0) };
+    }
+
+    Some(backend_ip)
+}
+
+// Returns the backend of the client in the lookup table of a service. Backends that are
+// not up are skipped by trying the following entries.
+fn lookup_service_backend(service_id: u32, client_ip: u32, client_port: u16) -> Option<u32> {
+    let entry = maglev::flow_hash(client_ip, client_port) % MAGLEV_TABLE_SIZE as u32;
+    for lookup in 0..MAX_SERVICE_LOOKUPS {
+        let index = (entry + lookup) % MAGLEV_TABLE_SIZE as u32;
+        let backend_ip =
+            match unsafe { SERVICE_TABLES.get(service_id * MAGLEV_TABLE_SIZE as u32 + index) } {
+                Some(&backend_ip) if backend_ip != 0 => backend_ip,
+                _ => return None,
+            };
+        if backend_state(backend_ip) == BACKEND_UP {
+            return Some(backend_ip);
+        }
+        increment_counter(&raw mut COUNTERS, COUNTER_UNAVAILABLE_BACKEND_SKIPPED);
+    }
+
+    None
+}
+
+#[inline(always)]
+fn backend_state(backend_ip: u32) -> u32 {
+    match unsafe { BACKEND_STATES.get(&backend_ip) } {
+        Some(&state) => state,
+        None => BACKEND_UP,
+    }
+}
+
+#[inline(always)]
+fn count_service_packet(backend_ip: u32) {
+    match unsafe { SERVICE_BACKEND_PACKETS.get_ptr_mut(&backend_ip) } {
+        Some(packets) => unsafe { *packets += 1 },
+        None => {
+            let _ = unsafe { SERVICE_BACKEND_PACKETS.insert(&backend_ip, &1, 0) };
+        }
+    }
+}
+
+// Counts a packet of the address in the sketch of this CPU
+#[inline(always)]
+fn count_heavy_hitter(index: u32, addr: u32) {
+    if let Some(sketch) = unsafe { HEAVY_HITTERS.get_ptr_mut(index) } {
+        sketch::count(unsafe { &mut *sketch }, addr);
+    }
+}
+
+// Records the client in the registers of this CPU
+#[inline(always)]
+fn count_unique_client(client_ip: u32) {
+    if let Some(registers) = unsafe { CLIENT_REGISTERS.get_ptr_mut(0) } {
+        hyperloglog::add(unsafe { &mut *registers }, client_ip);
+    }
+}
+
+// Counts a packet forwarded between the backend and the client, given their addresses
+// in network byte order
+fn count_traffic(backend_ip_be: u32, client_ip_be: u32, bytes: u64, response: bool) {
+    let now = unsafe { bpf_ktime_get_ns() };
+    add_traffic(
+        &raw mut BACKEND_TRAFFIC,
+        u32::from_be(backend_ip_be),
+        bytes,
+        response,
+        now,
+    );
+
+    let prefix_len = read!(CONFIG, CONFIG_TRAFFIC_CLIENT_PREFIX_LEN);
+    if prefix_len != 0 {
+        let mask = u32::MAX << (32 - prefix_len.min(32));
+        let net = u32::from_be(client_ip_be) & mask;
+        add_traffic(&raw mut CLIENT_PREFIX_TRAFFIC, net, bytes, response, now);
+    }
+}
+
+// Adds a packet to the counter of the key on this CPU
+#[inline(always)]
+fn add_traffic(
+    counters: *mut PerCpuHashMap<u32, TrafficCounter>,
+    key: u32,
+    bytes: u64,
+    response: bool,
+    now: u64,
+) {
+    let Some(counter) = (unsafe { (*counters).get_ptr_mut(&key) }) else {
+        let counter = match response {
+            true => TrafficCounter {
+                requests: 0,
+                request_bytes: 0,
+                responses: 1,
+                response_bytes: bytes,
+                last_seen: now,
+            },
+            false => TrafficCounter {
+                requests: 1,
+                request_bytes: bytes,
+                responses: 0,
+                response_bytes: 0,
+                last_seen: now,
+            },
+        };
+        // Fails while the map is full, until the controller evicts idle entries
+        let _ = unsafe { (*counters).insert(&key, &counter, 0) };
+        return;
+    };
+
+    unsafe {
+        if response {
+            (*counter).responses += 1;
+            (*counter).response_bytes += bytes;
+        } else {
+            (*counter).requests += 1;
+            (*counter).request_bytes += bytes;
+        }
+        (*counter).last_seen = now;
+    }
+}
+
+// Returns the new address of a backend with an unexpired remap, counting the hit against
+// the remap.
+fn remapped_backend(ip: u32) -> Option<u32> {
+    let remap = match unsafe { BACKEND_REMAPS.get(&ip) } {
+        Some(remap) => remap as *const BackendRemap as *mut BackendRemap,
+        None => return None,
+    };
+
+    let expires_at = unsafe { (*remap).expires_at };
+    if expires_at != 0 && expires_at <= unsafe { bpf_ktime_get_ns() } {
+        return None;
+    }
+
+    // Remaps are shared by all CPUs
+    unsafe { AtomicU64::from_ptr(&raw mut (*remap).hits) }.fetch_add(1, Ordering::Relaxed);
+    Some(unsafe { (*remap).backend })
+}
+
+// Returns true if the address is in an unexpired blocklist entry, counting the hit
+// against the entry.
+fn is_blocked(ip: u32) -> bool {
+    let key = Key::new(32, ip.to_be());
+    let entry = match unsafe { BLOCKLIST.get(&key) } {
+        Some(entry) => entry as *const BlocklistEntry as *mut BlocklistEntry,
+        None => return false,
+    };
+
+    let expires_at = unsafe { (*entry).expires_at };
+    if expires_at != 0 && expires_at <= unsafe { bpf_ktime_get_ns() } {
+        return false;
+    }
+
+    // Entries are shared by all CPUs
+    unsafe { AtomicU64::from_ptr(&raw mut (*entry).hits) }.fetch_add(1, Ordering::Relaxed);
+    true
+}
+
+// Takes a token from the bucket of the client, returning true if the bucket is empty
+fn is_rate_limited(ip: u32) -> bool {
+    let key = Key::new(32, ip.to_be());
+    let (rate, burst) = match unsafe { CLIENT_RATE_LIMITS.get(&key) } {
+        Some(limit) => (limit.rate, limit.burst),
+        None => (
+            read!(CONFIG, CONFIG_CLIENT_RATE_LIMIT),
+            read!(CONFIG, CONFIG_CLIENT_BURST),
+        ),
+    };
+
+    !take_token(&raw mut CLIENT_BUCKETS, ip, rate, burst)
+}
+
+// Takes a token from the bucket of the backend, returning true if the bucket is empty
+fn is_backend_overloaded(ip: u32) -> bool {
+    let key = Key::new(32, ip.to_be());
+    let Some(limit) = (unsafe { BACKEND_RATE_LIMITS.get(&key) }) else {
+        return false;
+    };
+
+    !take_token(&raw mut BACKEND_BUCKETS, ip, limit.rate, limit.burst)
+}
+
+// Takes a token from a token bucket refilled at `rate` packets per second, holding at
+// most `burst` packets, or `rate` if zero. Returns false if the bucket is empty. Buckets
+// are shared by all CPUs and updated without synchronization, so limits are approximate
+// when packets with the same key are processed on several CPUs at once.
+#[inline(always)]
+fn take_token(buckets: *mut LruHashMap<u32, TokenBucket>, key: u32, rate: u64, burst: u64) -> bool {
+    if rate == 0 {
+        return true;
+    }
+    let burst = if burst == 0 { rate } else { burst };
+    let capacity = burst.saturating_mul(TOKEN_SCALE);
+    let now = unsafe { bpf_ktime_get_ns() };
+
+    // New buckets start full
+    let Some(bucket) = (unsafe { (*buckets).get_ptr_mut(&key) }) else {
+        let bucket = TokenBucket {
+            tokens: capacity - TOKEN_SCALE,
+            updated_at: now,
+            dropped: 0,
+        };
+        let _ = unsafe { (*buckets).insert(&key, &bucket, 0) };
+        return true;
+    };
+
+    unsafe {
+        let elapsed = match now.checked_sub((*bucket).updated_at) {
+            Some(elapsed) if elapsed < MAX_REFILL_NS => elapsed,
+            Some(_) => MAX_REFILL_NS,
+            None => 0,
+        };
+        let refill = elapsed.saturating_mul(rate);
+        let tokens = match (*bucket).tokens.saturating_add(refill) {
+            tokens if tokens > capacity => capacity,
+            tokens => tokens,
+        };
+        (*bucket).updated_at = now;
+
+        if tokens < TOKEN_SCALE {
+            (*bucket).tokens = tokens;
+            (*bucket).dropped += 1;
+            return false;
+        }
+        (*bucket).tokens = tokens - TOKEN_SCALE;
+    }
+
+    true
 }
 
 fn is_link_local_ip(ip: u32) -> bool {
@@ -127,10 +1185,16 @@ fn is_from_backend_server(ip: u32) -> bool {
     (ip & mask) == net
 }
 
-fn try_forward_packet(ctx: &XdpContext) -> Result<u32, ()> {
+fn try_forward_packet(
+    ctx: &XdpContext,
+    ip_header_len: usize,
+    egress: u32,
+    from_backend: bool,
+    client_token: Option<u64>,
+) -> Result<u32, ()> {
     let ethhdr: *mut EthHdr = ptr_at_mut(ctx, 0)?;
     let ipv4hdr: *mut Ipv4Hdr = ptr_at_mut(ctx, EthHdr::LEN)?;
-    let udphdr: *mut UdpHdr = ptr_at_mut(ctx, EthHdr::LEN + Ipv4Hdr::LEN)?;
+    let udphdr: *mut UdpHdr = ptr_at_mut(ctx, EthHdr::LEN + ip_header_len)?;
 
     //
     // Step 1: Parse IP addresses from packet + payload
@@ -139,8 +1203,53 @@ fn try_forward_packet(ctx: &XdpContext) -> Result<u32, ()> {
     // Get IP addresses for forwarded packet
     let router_ip_be = unsafe { (*ipv4hdr).dst_addr };
     let source_ip_be = unsafe { (*ipv4hdr).src_addr };
-    let payload: *mut u32 = ptr_at_mut(&ctx, EthHdr::LEN + Ipv4Hdr::LEN + UdpHdr::LEN)?;
-    let target_ip_be = unsafe { *payload };
+    let payload: *mut u32 = ptr_at_mut(ctx, EthHdr::LEN + ip_header_len + UdpHdr::LEN)?;
+    let header_be = unsafe { *payload };
+
+    // Routing headers of clients may name a service instead of a backend
+    let mut target_ip_be = header_be;
+    let header = u32::from_be(header_be);
+    let to_service = !from_backend && header & SERVICE_MASK == SERVICE_NET;
+    if to_service {
+        let source_port = u16::from_be(unsafe { (*udphdr).source });
+        let Some(backend_ip) = service_backend(header, u32::from_be(source_ip_be), source_port)
+        else {
+            increment_counter(&raw mut COUNTERS, COUNTER_SERVICE_UNAVAILABLE_DROPPED);
+            return Ok(XDP_DROP);
+        };
+        target_ip_be = backend_ip.to_be();
+    }
+
+    // Backends that moved are reached at their new address
+    if !from_backend {
+        if let Some(backend_ip) = remapped_backend(u32::from_be(target_ip_be)) {
+            target_ip_be = backend_ip.to_be();
+            increment_counter(&raw mut COUNTERS, COUNTER_BACKEND_REMAPPED);
+        }
+    }
+
+    if !from_backend {
+        count_heavy_hitter(SKETCH_TARGETS, u32::from_be(target_ip_be));
+    }
+
+    // Shed load of backends receiving more than their rate limit
+    if !from_backend && is_backend_overloaded(u32::from_be(target_ip_be)) {
+        increment_counter(&raw mut COUNTERS, COUNTER_BACKEND_OVERLOAD_DROPPED);
+        return Ok(XDP_DROP);
+    }
+    if to_service {
+        count_service_packet(u32::from_be(target_ip_be));
+    }
+
+    // Account the packet to its backend and client, at the length it is forwarded with
+    let ip_len = u16::from_be(unsafe { (*ipv4hdr).tot_len }) as u64;
+    if from_backend {
+        count_traffic(source_ip_be, target_ip_be, ip_len, true);
+        count_unique_client(u32::from_be(target_ip_be));
+    } else {
+        count_traffic(target_ip_be, source_ip_be, ip_len, false);
+        count_unique_client(u32::from_be(source_ip_be));
+    }
 
     //
     // Step 2: Rewrite source and destination IP of forwarded packet
@@ -177,75 +1286,276 @@ fn try_forward_packet(ctx: &XdpContext) -> Result<u32, ()> {
         udp_csum_ne = update_udp_checksum(udp_csum_ne, old_upper, new_upper);
         udp_csum_ne = update_udp_checksum(udp_csum_ne, old_lower, new_lower);
     }
-    unsafe {
-        let old = u32::from_be(target_ip_be).to_be_bytes();
-        let old_upper = u16::from_be_bytes([old[0], old[1]]);
-        let old_lower = u16::from_be_bytes([old[2], old[3]]);
-        let new = u32::from_be(source_ip_be).to_be_bytes();
-        let new_upper = u16::from_be_bytes([new[0], new[1]]);
-        let new_lower = u16::from_be_bytes([new[2], new[3]]);
+    if let Some(token) = client_token {
+        // The routing header was grown to fit the token, with zeros after the target
+        let header: *mut [u8; CLIENT_TOKEN_LEN] =
+            ptr_at_mut(ctx, EthHdr::LEN + ip_header_len + UdpHdr::LEN)?;
+        unsafe {
+            let new = token.to_be_bytes();
+            udp_csum_ne = update_udp_checksum_words(udp_csum_ne, &*header, &new);
+            *header = new;
+        }
+    } else {
+        unsafe {
+            let old = u32::from_be(header_be).to_be_bytes();
+            let old_upper = u16::from_be_bytes([old[0], old[1]]);
+            let old_lower = u16::from_be_bytes([old[2], old[3]]);
+            let new = u32::from_be(source_ip_be).to_be_bytes();
+            let new_upper = u16::from_be_bytes([new[0], new[1]]);
+            let new_lower = u16::from_be_bytes([new[2], new[3]]);
 
-        *payload = source_ip_be;
+            *payload = source_ip_be;
 
-        // Patch UDP checksum for these changes
-        udp_csum_ne = update_udp_checksum(udp_csum_ne, old_upper, new_upper);
-        udp_csum_ne = update_udp_checksum(udp_csum_ne, old_lower, new_lower);
+            // Patch UDP checksum for these changes
+            udp_csum_ne = update_udp_checksum(udp_csum_ne, old_upper, new_upper);
+            udp_csum_ne = update_udp_checksum(udp_csum_ne, old_lower, new_lower);
+        }
+    }
+    unsafe {
+        // The IP checksum is recalculated below and TTL is not part of the UDP checksum
+        (*ipv4hdr).ttl -= 1;
     }
 
     //
     // Step 3: Rewrite source and destination MAC address of forwarded packet
     //
 
+    let source_mac = read!(SOURCE_MAC_ADDRESS, egress);
+    let gateway_mac = read!(GATEWAY_MAC_ADDRESS, egress);
+
     unsafe {
-        (*ethhdr).src_addr = (*ethhdr).dst_addr;
-        (*ethhdr).dst_addr = get_gateway_mac_address();
+        (*ethhdr).src_addr = mac_to_bytes(source_mac);
+        (*ethhdr).dst_addr = mac_to_bytes(gateway_mac);
     }
 
     //
     // Step 4: Fix checksums
     //
 
+    let ip_csum = calculate_ip_checksum(ctx, ip_header_len)?;
+
+    // Packets sent without checksum are forwarded without one, unless configured
+    // otherwise. The checksum field must be zero while calculating the checksum.
+    if udp_csum_ne == UDP_NO_CHECKSUM
+        && read!(CONFIG, CONFIG_UDP_ZERO_CHECKSUM_POLICY) == UDP_ZERO_CHECKSUM_CALCULATE
+    {
+        if let Some(csum) = calculate_udp_checksum(ctx, ip_header_len)? {
+            udp_csum_ne = csum;
+            increment_counter(&raw mut COUNTERS, COUNTER_UDP_CHECKSUM_ADDED);
+        }
+    }
+
     unsafe {
-        (*ipv4hdr).check = calculate_ip_checksum(&*(ipv4hdr.cast()));
+        (*ipv4hdr).check = ip_csum;
         (*udphdr).check = u16::to_be(udp_csum_ne);
     }
 
-    Ok(xdp_action::XDP_TX)
+    //
+    // Step 5: Send packet out of the interface facing its destination
+    //
+
+    if is_two_interface_mode() {
+        return unsafe { REDIRECT_INTERFACES.redirect(egress, 0) }.map_err(|_| ());
+    }
+
+    Ok(XDP_TX)
+}
+
+// Turns the packet into an ICMP Time Exceeded message to its sender and sends it back
+// out of the interface it arrived on.
+fn send_time_exceeded(ctx: &XdpContext, ip_header_len: usize, ingress: u32) -> Result<u32, ()> {
+    let ipv4hdr: *const Ipv4Hdr = ptr_at_mut(ctx, EthHdr::LEN)?;
+    let router_ip_be = unsafe { (*ipv4hdr).dst_addr };
+    let source_ip_be = unsafe { (*ipv4hdr).src_addr };
+
+    // Make room for the new IPv4 and ICMP headers in front of the original IPv4 header,
+    // and cut the original datagram after its first 8 bytes
+    let icmp_len = IcmpHdr::LEN + ip_header_len + ICMP_ORIGINAL_DATAGRAM_LEN;
+    let new_len = EthHdr::LEN + Ipv4Hdr::LEN + icmp_len;
+
+    if unsafe { bpf_xdp_adjust_head(ctx.ctx, -((Ipv4Hdr::LEN + IcmpHdr::LEN) as i32)) } != 0 {
+        return Err(());
+    }
+    let delta = new_len as i32 - (ctx.data_end() - ctx.data()) as i32;
+    if unsafe { bpf_xdp_adjust_tail(ctx.ctx, delta) } != 0 {
+        return Err(());
+    }
+
+    let ethhdr: *mut EthHdr = ptr_at_mut(ctx, 0)?;
+    let ipv4hdr: *mut Ipv4Hdr = ptr_at_mut(ctx, EthHdr::LEN)?;
+    let icmphdr: *mut IcmpHdr = ptr_at_mut(ctx, EthHdr::LEN + Ipv4Hdr::LEN)?;
+
+    let source_mac = read!(SOURCE_MAC_ADDRESS, ingress);
+    let gateway_mac = read!(GATEWAY_MAC_ADDRESS, ingress);
+
+    unsafe {
+        (*ethhdr).src_addr = mac_to_bytes(source_mac);
+        (*ethhdr).dst_addr = mac_to_bytes(gateway_mac);
+        (*ethhdr).ether_type = EtherType::Ipv4;
+
+        (*ipv4hdr).set_version(4);
+        (*ipv4hdr).set_ihl((Ipv4Hdr::LEN / 4) as u8);
+        (*ipv4hdr).tos = 0;
+        (*ipv4hdr).tot_len = u16::to_be((Ipv4Hdr::LEN + icmp_len) as u16);
+        (*ipv4hdr).id = 0;
+        (*ipv4hdr).frag_off = 0;
+        (*ipv4hdr).ttl = DEFAULT_TTL;
+        (*ipv4hdr).proto = IpProto::Icmp;
+        (*ipv4hdr).check = 0;
+        (*ipv4hdr).src_addr = router_ip_be;
+        (*ipv4hdr).dst_addr = source_ip_be;
+
+        (*icmphdr).type_ = ICMP_TIME_EXCEEDED;
+        (*icmphdr).code = 0;
+        (*icmphdr).checksum = 0;
+        (*icmphdr).un.reserved = [0; 4];
+    }
+
+    let ip_csum = calculate_ip_checksum(ctx, Ipv4Hdr::LEN)?;
+    let icmp_offset = EthHdr::LEN + Ipv4Hdr::LEN;
+    let icmp_sum = sum_words::<MAX_ICMP_TIME_EXCEEDED_LEN>(ctx, icmp_offset, icmp_len)?;
+
+    unsafe {
+        (*ipv4hdr).check = ip_csum;
+        (*icmphdr).checksum = u16::to_be(fold_checksum(icmp_sum));
+    }
+
+    Ok(XDP_TX)
+}
+
+// Turns the packet into a challenge to its sender holding the cookie, and sends it back
+// out of the client interface. The caller makes sure the challenge is no longer than
+// the packet.
+fn send_challenge(
+    ctx: &XdpContext,
+    ip_header_len: usize,
+    cookie: &[u8; COOKIE_LEN],
+) -> Result<u32, ()> {
+    let udp_len = UdpHdr::LEN + CHALLENGE_LEN;
+    let new_len = EthHdr::LEN + ip_header_len + udp_len;
+    let delta = new_len as i32 - (ctx.data_end() - ctx.data()) as i32;
+    if unsafe { bpf_xdp_adjust_tail(ctx.ctx, delta) } != 0 {
+        return Err(());
+    }
+
+    let ethhdr: *mut EthHdr = ptr_at_mut(ctx, 0)?;
+    let ipv4hdr: *mut Ipv4Hdr = ptr_at_mut(ctx, EthHdr::LEN)?;
+    let udphdr: *mut UdpHdr = ptr_at_mut(ctx, EthHdr::LEN + ip_header_len)?;
+    let payload_offset = EthHdr::LEN + ip_header_len + UdpHdr::LEN;
+    let magic: *mut [u8; CHALLENGE_LEN - COOKIE_LEN] = ptr_at_mut(ctx, payload_offset)?;
+    let challenge_cookie: *mut [u8; COOKIE_LEN] =
+        ptr_at_mut(ctx, payload_offset + CHALLENGE_LEN - COOKIE_LEN)?;
+
+    let source_mac = read!(SOURCE_MAC_ADDRESS, CLIENT_INTERFACE);
+    let gateway_mac = read!(GATEWAY_MAC_ADDRESS, CLIENT_INTERFACE);
+
+    unsafe {
+        (*ethhdr).src_addr = mac_to_bytes(source_mac);
+        (*ethhdr).dst_addr = mac_to_bytes(gateway_mac);
+
+        mem::swap(&mut (*ipv4hdr).src_addr, &mut (*ipv4hdr).dst_addr);
+        (*ipv4hdr).tot_len = u16::to_be((ip_header_len + udp_len) as u16);
+        (*ipv4hdr).id = 0;
+        (*ipv4hdr).frag_off = 0;
+        (*ipv4hdr).ttl = DEFAULT_TTL;
+
+        mem::swap(&mut (*udphdr).source, &mut (*udphdr).dest);
+        (*udphdr).len = u16::to_be(udp_len as u16);
+        (*udphdr).check = 0;
+
+        *magic = CHALLENGE_MAGIC.to_be_bytes();
+        *challenge_cookie = *cookie;
+    }
+
+    let ip_csum = calculate_ip_checksum(ctx, ip_header_len)?;
+    let udp_csum = calculate_udp_checksum(ctx, ip_header_len)?.unwrap_or(UDP_NO_CHECKSUM);
+
+    unsafe {
+        (*ipv4hdr).check = ip_csum;
+        (*udphdr).check = u16::to_be(udp_csum);
+    }
+
+    Ok(XDP_TX)
 }
 
 #[inline(always)]
-fn get_gateway_mac_address() -> [u8; 6] {
-    let mac = read!(GATEWAY_MAC_ADDRESS, 0).to_be_bytes();
+fn mac_to_bytes(mac: u64) -> [u8; 6] {
+    let mac = mac.to_be_bytes();
     [mac[2], mac[3], mac[4], mac[5], mac[6], mac[7]]
 }
 
+// Calculates the checksum of the IPv4 header of the packet, including any options.
 #[inline(always)]
-fn calculate_ip_checksum(ipv4hdr: &[u8; 20]) -> u16 {
-    let mut checksum: u32 = 0;
-    for i in 0..10 {
-        checksum += u16::from_be_bytes([ipv4hdr[2 * i], ipv4hdr[2 * i + 1]]) as u32
-    }
+fn calculate_ip_checksum(ctx: &XdpContext, header_len: usize) -> Result<u16, ()> {
+    let mut checksum = sum_words::<MAX_IPV4_HDR_LEN>(ctx, EthHdr::LEN, header_len)?;
+
     // subtract existing checksum from header
-    checksum -= u16::from_be_bytes([ipv4hdr[10], ipv4hdr[11]]) as u32;
+    let check: *const [u8; 2] = ptr_at_mut(ctx, EthHdr::LEN + 10)?;
+    checksum -= u16::from_be_bytes(unsafe { *check }) as u32;
 
-    // clear any overflow bits
-    checksum = (checksum & 0xffff) + (checksum >> 16);
-    checksum = (checksum & 0xffff) + (checksum >> 16);
+    Ok(u16::to_be(fold_checksum(checksum)))
+}
+
+// Calculates the full UDP checksum of the packet in native byte order, including the
+// IPv4 pseudo header. Returns None for datagrams too long to checksum in XDP or not
+// contained in the frame, i.e. fragmented.
+#[inline(always)]
+fn calculate_udp_checksum(ctx: &XdpContext, ip_header_len: usize) -> Result<Option<u16>, ()> {
+    let ipv4hdr: *const Ipv4Hdr = ptr_at_mut(ctx, EthHdr::LEN)?;
+    let udphdr: *const UdpHdr = ptr_at_mut(ctx, EthHdr::LEN + ip_header_len)?;
 
-    u16::to_be(!(checksum as u16))
+    let udp_offset = EthHdr::LEN + ip_header_len;
+    let udp_len = u16::from_be(unsafe { (*udphdr).len }) as usize;
+    if udp_len > MAX_UDP_CHECKSUM_LEN || udp_offset + udp_len > ctx.data_end() - ctx.data() {
+        return Ok(None);
+    }
+
+    // pseudo header
+    let src = u32::from_be(unsafe { (*ipv4hdr).src_addr });
+    let dst = u32::from_be(unsafe { (*ipv4hdr).dst_addr });
+    let mut checksum: u32 = (src >> 16) + (src & 0xffff) + (dst >> 16) + (dst & 0xffff);
+    checksum += IpProto::Udp as u32 + udp_len as u32;
+
+    // UDP header and payload
+    checksum += sum_words::<MAX_UDP_CHECKSUM_LEN>(ctx, udp_offset, udp_len)?;
+    if udp_len % 2 == 1 {
+        let byte: *const u8 = ptr_at_mut(ctx, udp_offset + udp_len - 1)?;
+        checksum += (unsafe { *byte } as u32) << 8;
+    }
+
+    Ok(Some(match fold_checksum(checksum) {
+        0 => 0xffff,
+        csum => csum,
+    }))
 }
 
-// Calculates updated UDP checksum when changing a word 'old' to 'new' in packet.
-// Algorithm: https://www.rfc-editor.org/rfc/rfc1624
+// Sums the 16-bit words in `len` bytes of the packet starting at `offset`, ignoring a
+// trailing odd byte. At most `MAX` bytes are summed, to keep the verifier happy.
 #[inline(always)]
-fn update_udp_checksum(csum: u16, old: u16, new: u16) -> u16 {
-    !((!csum).wrapping_sub(old).wrapping_add(new))
+fn sum_words<const MAX: usize>(ctx: &XdpContext, offset: usize, len: usize) -> Result<u32, ()> {
+    let mut sum: u32 = 0;
+    for i in 0..MAX / 2 {
+        if 2 * i + 2 > len {
+            break;
+        }
+        let word: *const [u8; 2] = ptr_at_mut(ctx, offset + 2 * i)?;
+        sum += u16::from_be_bytes(unsafe { *word }) as u32;
+    }
+
+    Ok(sum)
 }
 
 //
 // Helpers
 //
 
+#[inline(always)]
+fn increment_counter(counter: *mut PerCpuArray<u64>, index: u32) {
+    if let Some(value) = unsafe { (*counter).get_ptr_mut(index) } {
+        unsafe { *value += 1 };
+    }
+}
+
 #[inline(always)]
 fn ptr_at_mut<T>(ctx: &XdpContext, offset: usize) -> Result<*mut T, ()> {
     let start = ctx.data();
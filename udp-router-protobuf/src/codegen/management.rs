@@ -1,7 +1,7 @@
 // This file is @generated by prost-build.
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct GetStatsRequest {}
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetStatsResponse {
     #[prost(uint64, tag = "1")]
     pub total_packets: u64,
@@ -9,6 +9,85 @@ pub struct GetStatsResponse {
     pub client_to_server_packets: u64,
     #[prost(uint64, tag = "3")]
     pub server_to_client_packets: u64,
+    #[prost(uint64, tag = "4")]
+    pub non_unicast_dropped_packets: u64,
+    #[prost(uint64, tag = "5")]
+    pub ip_options_dropped_packets: u64,
+    #[prost(uint64, tag = "6")]
+    pub udp_checksum_added_packets: u64,
+    #[prost(uint64, tag = "7")]
+    pub ip_length_dropped_packets: u64,
+    #[prost(uint64, tag = "8")]
+    pub udp_length_dropped_packets: u64,
+    #[prost(uint64, tag = "9")]
+    pub short_routing_header_dropped_packets: u64,
+    #[prost(uint64, tag = "10")]
+    pub ttl_expired_dropped_packets: u64,
+    #[prost(uint64, tag = "11")]
+    pub icmp_time_exceeded_sent_packets: u64,
+    #[prost(uint64, tag = "12")]
+    pub fragments_dropped_packets: u64,
+    #[prost(uint64, tag = "13")]
+    pub fragments_passed_packets: u64,
+    #[prost(uint64, tag = "14")]
+    pub first_fragments_forwarded_packets: u64,
+    #[prost(uint64, tag = "15")]
+    pub target_unspecified_dropped_packets: u64,
+    #[prost(uint64, tag = "16")]
+    pub target_loopback_dropped_packets: u64,
+    #[prost(uint64, tag = "17")]
+    pub target_multicast_dropped_packets: u64,
+    #[prost(uint64, tag = "18")]
+    pub target_broadcast_dropped_packets: u64,
+    #[prost(uint64, tag = "19")]
+    pub target_link_local_dropped_packets: u64,
+    #[prost(uint64, tag = "20")]
+    pub target_local_network_dropped_packets: u64,
+    #[prost(uint64, tag = "21")]
+    pub target_router_dropped_packets: u64,
+    #[prost(uint64, tag = "23")]
+    pub rate_limited_dropped_packets: u64,
+    #[prost(uint64, tag = "22")]
+    pub blocklist_dropped_packets: u64,
+    #[prost(uint64, tag = "24")]
+    pub backend_overload_dropped_packets: u64,
+    #[prost(uint64, tag = "26")]
+    pub header_auth_dropped_packets: u64,
+    #[prost(uint64, tag = "27")]
+    pub client_token_dropped_packets: u64,
+    #[prost(uint64, tag = "28")]
+    pub unseen_client_dropped_packets: u64,
+    #[prost(uint64, tag = "29")]
+    pub amplification_dropped_packets: u64,
+    #[prost(uint64, tag = "30")]
+    pub challenges_sent_packets: u64,
+    #[prost(uint64, tag = "31")]
+    pub cookies_verified_packets: u64,
+    #[prost(uint64, tag = "32")]
+    pub cookie_dropped_packets: u64,
+    #[prost(uint64, tag = "33")]
+    pub service_unavailable_dropped_packets: u64,
+    #[prost(uint64, tag = "34")]
+    pub unavailable_backend_skipped_packets: u64,
+    #[prost(uint64, tag = "35")]
+    pub backend_remapped_packets: u64,
+    /// Distinct clients in the last complete minute, estimated
+    #[prost(uint64, tag = "36")]
+    pub unique_clients: u64,
+    #[prost(message, repeated, tag = "25")]
+    pub backends: ::prost::alloc::vec::Vec<BackendStats>,
+}
+/// Statistics of a backend the router has forwarded packets to
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct BackendStats {
+    #[prost(uint32, tag = "1")]
+    pub addr: u32,
+    /// Packets dropped as the backend exceeded its rate limit
+    #[prost(uint64, tag = "2")]
+    pub shed_packets: u64,
+    /// Packets forwarded to the backend through a service
+    #[prost(uint64, tag = "3")]
+    pub service_packets: u64,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct SetLocalNetAndMaskRequest {
@@ -28,6 +107,741 @@ pub struct SetBackendNetAndMaskRequest {
 pub struct SetGatewayMacAddressRequest {
     #[prost(uint64, tag = "1")]
     pub mac: u64,
+    #[prost(enumeration = "InterfaceSide", tag = "2")]
+    pub side: i32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetSourceMacAddressRequest {
+    #[prost(uint64, tag = "1")]
+    pub mac: u64,
+    #[prost(enumeration = "InterfaceSide", tag = "2")]
+    pub side: i32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetIpOptionsPolicyRequest {
+    #[prost(enumeration = "IpOptionsPolicy", tag = "1")]
+    pub policy: i32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetUdpZeroChecksumPolicyRequest {
+    #[prost(enumeration = "UdpZeroChecksumPolicy", tag = "1")]
+    pub policy: i32,
+}
+/// Minimum length of the routing header at the start of the UDP payload. Shorter
+/// packets are dropped. Values below 4 bytes, the length of the backend address,
+/// are ignored.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetMinRoutingHeaderLenRequest {
+    #[prost(uint32, tag = "1")]
+    pub len: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetTtlExpiredPolicyRequest {
+    #[prost(enumeration = "TtlExpiredPolicy", tag = "1")]
+    pub policy: i32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetFragmentPolicyRequest {
+    #[prost(enumeration = "FragmentPolicy", tag = "1")]
+    pub policy: i32,
+}
+/// Client prefix whose traffic is dropped
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct BlocklistEntry {
+    #[prost(uint32, tag = "1")]
+    pub net: u32,
+    #[prost(uint32, tag = "2")]
+    pub prefix_len: u32,
+    /// Seconds until the entry expires, at most ten years, or zero if it never does
+    #[prost(uint64, tag = "3")]
+    pub expires_in_secs: u64,
+    /// Packets dropped due to the entry. Ignored when adding entries.
+    #[prost(uint64, tag = "4")]
+    pub hits: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddBlocklistEntriesRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<BlocklistEntry>,
+}
+/// Only the prefixes of the entries are used
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RemoveBlocklistEntriesRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<BlocklistEntry>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RemoveBlocklistEntriesResponse {
+    #[prost(uint32, tag = "1")]
+    pub removed: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListBlocklistEntriesRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBlocklistEntriesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<BlocklistEntry>,
+}
+/// Packets to the old address of a backend are sent to its new address, e.g. after the
+/// backend moved to another host
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct BackendRemap {
+    #[prost(uint32, tag = "1")]
+    pub old_backend: u32,
+    #[prost(uint32, tag = "2")]
+    pub new_backend: u32,
+    /// Seconds until the remap expires, at most ten years, or zero if it never does
+    #[prost(uint64, tag = "3")]
+    pub expires_in_secs: u64,
+    /// Packets sent to the new address. Ignored when adding remaps.
+    #[prost(uint64, tag = "4")]
+    pub hits: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddBackendRemapsRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub remaps: ::prost::alloc::vec::Vec<BackendRemap>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RemoveBackendRemapsRequest {
+    #[prost(uint32, repeated, tag = "1")]
+    pub old_backends: ::prost::alloc::vec::Vec<u32>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RemoveBackendRemapsResponse {
+    #[prost(uint32, tag = "1")]
+    pub removed: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListBackendRemapsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBackendRemapsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub remaps: ::prost::alloc::vec::Vec<BackendRemap>,
+}
+/// Packets per second allowed from each client without a prefix rate limit, and the
+/// maximum burst. A zero rate means unlimited and a zero burst the same as the rate.
+/// Both are at most 1000000000.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetClientRateLimitRequest {
+    #[prost(uint64, tag = "1")]
+    pub rate: u64,
+    #[prost(uint64, tag = "2")]
+    pub burst: u64,
+}
+/// Rate limit of each client in a prefix, overriding the global rate limit, or of each
+/// backend in a prefix. The rate and burst are at most 1000000000.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct PrefixRateLimit {
+    #[prost(uint32, tag = "1")]
+    pub net: u32,
+    #[prost(uint32, tag = "2")]
+    pub prefix_len: u32,
+    #[prost(uint64, tag = "3")]
+    pub rate: u64,
+    #[prost(uint64, tag = "4")]
+    pub burst: u64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetPrefixRateLimitRequest {
+    #[prost(message, optional, tag = "1")]
+    pub limit: ::core::option::Option<PrefixRateLimit>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RemovePrefixRateLimitRequest {
+    #[prost(uint32, tag = "1")]
+    pub net: u32,
+    #[prost(uint32, tag = "2")]
+    pub prefix_len: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListPrefixRateLimitsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPrefixRateLimitsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub limits: ::prost::alloc::vec::Vec<PrefixRateLimit>,
+}
+/// Clients with the most packets dropped by their rate limit. All clients with dropped
+/// packets are returned if the limit is zero.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetTopRateLimitedClientsRequest {
+    #[prost(uint32, tag = "1")]
+    pub limit: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RateLimitedClient {
+    #[prost(uint32, tag = "1")]
+    pub addr: u32,
+    #[prost(uint64, tag = "2")]
+    pub dropped_packets: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTopRateLimitedClientsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub clients: ::prost::alloc::vec::Vec<RateLimitedClient>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetHeaderAuthPolicyRequest {
+    #[prost(enumeration = "HeaderAuthPolicy", tag = "1")]
+    pub policy: i32,
+}
+/// Makes the key the current key of routing header tags or client tokens. The current key
+/// is still accepted as the previous key until it is retired.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RotateKeyRequest {
+    /// 16 byte SipHash key
+    #[prost(bytes = "vec", tag = "1")]
+    pub key: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RetirePreviousKeyRequest {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetClientTokenPolicyRequest {
+    #[prost(enumeration = "ClientTokenPolicy", tag = "1")]
+    pub policy: i32,
+}
+/// Responses are only forwarded to clients that sent a packet within the window. Zero
+/// forwards responses to any client.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetSeenClientWindowRequest {
+    #[prost(uint32, tag = "1")]
+    pub window_secs: u32,
+}
+/// Responses to a client are dropped once their bytes exceed the ratio times the bytes of
+/// its requests. Zero disables the limit. The ratio is at most 1000.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetMaxAmplificationRequest {
+    #[prost(uint32, tag = "1")]
+    pub ratio: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetChallengePolicyRequest {
+    #[prost(enumeration = "ChallengePolicy", tag = "1")]
+    pub policy: i32,
+}
+/// Routing headers in 240.0.0.0/8 name the service with the ID in their lower 24 bits.
+/// Packets to a service go to a backend of its pool picked by the client address and
+/// port. Canaries get a percentage of the clients of the service.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ServiceBackendsRequest {
+    #[prost(uint32, tag = "1")]
+    pub service_id: u32,
+    #[prost(uint32, repeated, tag = "2")]
+    pub backends: ::prost::alloc::vec::Vec<u32>,
+    /// Whether the backends are in the canary pool
+    #[prost(bool, tag = "3")]
+    pub canary: bool,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetServiceCanaryPercentRequest {
+    #[prost(uint32, tag = "1")]
+    pub service_id: u32,
+    #[prost(uint32, tag = "2")]
+    pub canary_percent: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RemoveServiceBackendsResponse {
+    #[prost(uint32, tag = "1")]
+    pub removed: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Service {
+    #[prost(uint32, tag = "1")]
+    pub service_id: u32,
+    #[prost(uint32, repeated, tag = "2")]
+    pub backends: ::prost::alloc::vec::Vec<u32>,
+    #[prost(uint32, repeated, tag = "3")]
+    pub canaries: ::prost::alloc::vec::Vec<u32>,
+    #[prost(uint32, tag = "4")]
+    pub canary_percent: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListServicesRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListServicesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub services: ::prost::alloc::vec::Vec<Service>,
+}
+/// Backends of services are probed by the controller, and skipped by services while down
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct BackendHealth {
+    #[prost(uint32, tag = "1")]
+    pub addr: u32,
+    #[prost(bool, tag = "2")]
+    pub up: bool,
+    #[prost(uint64, tag = "3")]
+    pub probes_succeeded: u64,
+    #[prost(uint64, tag = "4")]
+    pub probes_failed: u64,
+    /// Round-trip time of the last answered probe in microseconds, or zero if none was
+    #[prost(uint64, tag = "5")]
+    pub last_rtt_micros: u64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetBackendHealthRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBackendHealthResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub backends: ::prost::alloc::vec::Vec<BackendHealth>,
+}
+/// Client flows, i.e. client addresses and ports, stay with the backend of a service they
+/// were sent to until idle for the timeout. Zero means the default of 60 seconds.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetFlowIdleTimeoutRequest {
+    #[prost(uint32, tag = "1")]
+    pub timeout_secs: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct Flow {
+    #[prost(uint32, tag = "1")]
+    pub client_ip: u32,
+    #[prost(uint32, tag = "2")]
+    pub client_port: u32,
+    #[prost(uint32, tag = "3")]
+    pub service_id: u32,
+    #[prost(uint32, tag = "4")]
+    pub backend: u32,
+    /// Milliseconds since the last packet of the flow
+    #[prost(uint64, tag = "5")]
+    pub idle_millis: u64,
+    #[prost(bool, tag = "6")]
+    pub pinned: bool,
+}
+/// Flows sent to the backend, or to any backend if zero. All flows are returned if the
+/// limit is zero.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListFlowsRequest {
+    #[prost(uint32, tag = "1")]
+    pub backend: u32,
+    #[prost(uint32, tag = "2")]
+    pub limit: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListFlowsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub flows: ::prost::alloc::vec::Vec<Flow>,
+}
+/// Makes flows sent to the backend, or to any backend if zero, forget their backend.
+/// Pinned flows are kept.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct FlushFlowsRequest {
+    #[prost(uint32, tag = "1")]
+    pub backend: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct FlushFlowsResponse {
+    #[prost(uint32, tag = "1")]
+    pub flushed: u32,
+}
+/// Pinned flows stay with their backend however long they are idle
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct PinFlowRequest {
+    #[prost(uint32, tag = "1")]
+    pub client_ip: u32,
+    #[prost(uint32, tag = "2")]
+    pub client_port: u32,
+    #[prost(uint32, tag = "3")]
+    pub service_id: u32,
+    #[prost(uint32, tag = "4")]
+    pub backend: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct UnpinFlowRequest {
+    #[prost(uint32, tag = "1")]
+    pub client_ip: u32,
+    #[prost(uint32, tag = "2")]
+    pub client_port: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct UnpinFlowResponse {
+    #[prost(bool, tag = "1")]
+    pub unpinned: bool,
+}
+/// Traffic is counted by client prefix of this length as well as by backend, or only by
+/// backend if zero. Changing the length clears the counters of client prefixes.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetTrafficClientPrefixLenRequest {
+    #[prost(uint32, tag = "1")]
+    pub prefix_len: u32,
+}
+/// Traffic forwarded to and from a backend or client prefix. Counters are removed after
+/// 10 minutes without packets.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct TrafficCounter {
+    #[prost(uint32, tag = "1")]
+    pub addr: u32,
+    #[prost(uint32, tag = "2")]
+    pub prefix_len: u32,
+    /// Packets and bytes forwarded from clients to backends
+    #[prost(uint64, tag = "3")]
+    pub requests: u64,
+    #[prost(uint64, tag = "4")]
+    pub request_bytes: u64,
+    /// Packets and bytes forwarded from backends to clients
+    #[prost(uint64, tag = "5")]
+    pub responses: u64,
+    #[prost(uint64, tag = "6")]
+    pub response_bytes: u64,
+    /// Milliseconds since the last packet
+    #[prost(uint64, tag = "7")]
+    pub idle_millis: u64,
+}
+/// Counters in order of address, starting from the page token, up to the limit. All
+/// counters are returned if the limit is zero.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListTrafficCountersRequest {
+    #[prost(enumeration = "TrafficKeyed", tag = "1")]
+    pub keyed: i32,
+    #[prost(uint32, tag = "2")]
+    pub page_token: u32,
+    #[prost(uint32, tag = "3")]
+    pub limit: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListTrafficCountersResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub counters: ::prost::alloc::vec::Vec<TrafficCounter>,
+    /// Page token of the next page, or zero if this is the last page
+    #[prost(uint32, tag = "2")]
+    pub next_page_token: u32,
+}
+/// Clients and backends with the most packets in the last complete window, up to the
+/// limit of each, or all if zero. At most 16 of each are kept.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetTopTalkersRequest {
+    #[prost(uint32, tag = "1")]
+    pub limit: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct TopTalker {
+    #[prost(uint32, tag = "1")]
+    pub addr: u32,
+    /// Estimated packets in the window, never less than the actual packets
+    #[prost(uint64, tag = "2")]
+    pub packets: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTopTalkersResponse {
+    /// Clients sending the most packets, in descending order of packets
+    #[prost(message, repeated, tag = "1")]
+    pub sources: ::prost::alloc::vec::Vec<TopTalker>,
+    /// Backends receiving the most packets from clients, in descending order of packets
+    #[prost(message, repeated, tag = "2")]
+    pub targets: ::prost::alloc::vec::Vec<TopTalker>,
+    #[prost(uint32, tag = "3")]
+    pub window_secs: u32,
+}
+/// Clients sending more packets per second than the rate in a window are added to the
+/// blocklist for the given number of seconds. A rate of zero disables blocking. The rate
+/// is at most 1000000000.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetTopTalkerBlockingRequest {
+    #[prost(uint64, tag = "1")]
+    pub packets_per_sec: u64,
+    #[prost(uint32, tag = "2")]
+    pub block_secs: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetBackendStateRequest {
+    #[prost(uint32, tag = "1")]
+    pub addr: u32,
+    #[prost(enumeration = "BackendState", tag = "2")]
+    pub state: i32,
+    #[prost(uint32, tag = "3")]
+    pub drain_timeout_secs: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct BackendMaintenance {
+    #[prost(uint32, tag = "1")]
+    pub addr: u32,
+    #[prost(enumeration = "BackendState", tag = "2")]
+    pub state: i32,
+    /// Seconds until a draining backend is disabled
+    #[prost(uint64, tag = "3")]
+    pub disabled_in_secs: u64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListBackendStatesRequest {}
+/// Backends that are not enabled
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBackendStatesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub backends: ::prost::alloc::vec::Vec<BackendMaintenance>,
+}
+/// Side of the router an interface faces. Only the client side is used unless the
+/// router is attached to a separate backend-facing interface.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum InterfaceSide {
+    Client = 0,
+    Backend = 1,
+}
+impl InterfaceSide {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Client => "CLIENT",
+            Self::Backend => "BACKEND",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CLIENT" => Some(Self::Client),
+            "BACKEND" => Some(Self::Backend),
+            _ => None,
+        }
+    }
+}
+/// Handling of IPv4 packets with header options
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum IpOptionsPolicy {
+    IpOptionsForward = 0,
+    IpOptionsDrop = 1,
+}
+impl IpOptionsPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::IpOptionsForward => "IP_OPTIONS_FORWARD",
+            Self::IpOptionsDrop => "IP_OPTIONS_DROP",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "IP_OPTIONS_FORWARD" => Some(Self::IpOptionsForward),
+            "IP_OPTIONS_DROP" => Some(Self::IpOptionsDrop),
+            _ => None,
+        }
+    }
+}
+/// Handling of UDP packets sent without checksum
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum UdpZeroChecksumPolicy {
+    UdpZeroChecksumKeep = 0,
+    UdpZeroChecksumCalculate = 1,
+}
+impl UdpZeroChecksumPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::UdpZeroChecksumKeep => "UDP_ZERO_CHECKSUM_KEEP",
+            Self::UdpZeroChecksumCalculate => "UDP_ZERO_CHECKSUM_CALCULATE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UDP_ZERO_CHECKSUM_KEEP" => Some(Self::UdpZeroChecksumKeep),
+            "UDP_ZERO_CHECKSUM_CALCULATE" => Some(Self::UdpZeroChecksumCalculate),
+            _ => None,
+        }
+    }
+}
+/// Handling of packets arriving with a TTL of 1 or less
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TtlExpiredPolicy {
+    TtlExpiredDrop = 0,
+    TtlExpiredIcmp = 1,
+}
+impl TtlExpiredPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::TtlExpiredDrop => "TTL_EXPIRED_DROP",
+            Self::TtlExpiredIcmp => "TTL_EXPIRED_ICMP",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TTL_EXPIRED_DROP" => Some(Self::TtlExpiredDrop),
+            "TTL_EXPIRED_ICMP" => Some(Self::TtlExpiredIcmp),
+            _ => None,
+        }
+    }
+}
+/// Handling of IPv4 fragments
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum FragmentPolicy {
+    FragmentsDrop = 0,
+    FragmentsPass = 1,
+    FragmentsForwardFirst = 2,
+}
+impl FragmentPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::FragmentsDrop => "FRAGMENTS_DROP",
+            Self::FragmentsPass => "FRAGMENTS_PASS",
+            Self::FragmentsForwardFirst => "FRAGMENTS_FORWARD_FIRST",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "FRAGMENTS_DROP" => Some(Self::FragmentsDrop),
+            "FRAGMENTS_PASS" => Some(Self::FragmentsPass),
+            "FRAGMENTS_FORWARD_FIRST" => Some(Self::FragmentsForwardFirst),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum HeaderAuthPolicy {
+    HeaderAuthDisabled = 0,
+    HeaderAuthRequired = 1,
+}
+impl HeaderAuthPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::HeaderAuthDisabled => "HEADER_AUTH_DISABLED",
+            Self::HeaderAuthRequired => "HEADER_AUTH_REQUIRED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "HEADER_AUTH_DISABLED" => Some(Self::HeaderAuthDisabled),
+            "HEADER_AUTH_REQUIRED" => Some(Self::HeaderAuthRequired),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ClientTokenPolicy {
+    ClientTokensDisabled = 0,
+    ClientTokensEnabled = 1,
+}
+impl ClientTokenPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::ClientTokensDisabled => "CLIENT_TOKENS_DISABLED",
+            Self::ClientTokensEnabled => "CLIENT_TOKENS_ENABLED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CLIENT_TOKENS_DISABLED" => Some(Self::ClientTokensDisabled),
+            "CLIENT_TOKENS_ENABLED" => Some(Self::ClientTokensEnabled),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ChallengePolicy {
+    ChallengesDisabled = 0,
+    ChallengesRequired = 1,
+}
+impl ChallengePolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::ChallengesDisabled => "CHALLENGES_DISABLED",
+            Self::ChallengesRequired => "CHALLENGES_REQUIRED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CHALLENGES_DISABLED" => Some(Self::ChallengesDisabled),
+            "CHALLENGES_REQUIRED" => Some(Self::ChallengesRequired),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TrafficKeyed {
+    TrafficByBackend = 0,
+    TrafficByClientPrefix = 1,
+}
+impl TrafficKeyed {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::TrafficByBackend => "TRAFFIC_BY_BACKEND",
+            Self::TrafficByClientPrefix => "TRAFFIC_BY_CLIENT_PREFIX",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TRAFFIC_BY_BACKEND" => Some(Self::TrafficByBackend),
+            "TRAFFIC_BY_CLIENT_PREFIX" => Some(Self::TrafficByClientPrefix),
+            _ => None,
+        }
+    }
+}
+/// Draining backends only receive packets of the clients they serve through services,
+/// until disabled after the drain timeout. Disabled backends receive no packets through
+/// services. Only backends of a service can be drained or disabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum BackendState {
+    BackendEnabled = 0,
+    BackendDraining = 1,
+    BackendDisabled = 2,
+}
+impl BackendState {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::BackendEnabled => "BACKEND_ENABLED",
+            Self::BackendDraining => "BACKEND_DRAINING",
+            Self::BackendDisabled => "BACKEND_DISABLED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "BACKEND_ENABLED" => Some(Self::BackendEnabled),
+            "BACKEND_DRAINING" => Some(Self::BackendDraining),
+            "BACKEND_DISABLED" => Some(Self::BackendDisabled),
+            _ => None,
+        }
+    }
 }
 /// Generated client implementations.
 pub mod router_service_client {
@@ -213,136 +1027,3346 @@ pub mod router_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod router_service_server {
-    #![allow(
-        unused_variables,
-        dead_code,
-        missing_docs,
-        clippy::wildcard_imports,
-        clippy::let_unit_value,
-    )]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with RouterServiceServer.
-    #[async_trait]
-    pub trait RouterService: std::marker::Send + std::marker::Sync + 'static {
-        async fn get_stats(
-            &self,
-            request: tonic::Request<super::GetStatsRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::GetStatsResponse>,
-            tonic::Status,
-        >;
-        async fn set_local_net_and_mask(
-            &self,
-            request: tonic::Request<super::SetLocalNetAndMaskRequest>,
-        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
-        async fn set_backend_net_and_mask(
-            &self,
-            request: tonic::Request<super::SetBackendNetAndMaskRequest>,
-        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
-        async fn set_gateway_mac_address(
-            &self,
-            request: tonic::Request<super::SetGatewayMacAddressRequest>,
-        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
-    }
-    #[derive(Debug)]
-    pub struct RouterServiceServer<T> {
-        inner: Arc<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-        max_decoding_message_size: Option<usize>,
-        max_encoding_message_size: Option<usize>,
-    }
-    impl<T> RouterServiceServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
+        pub async fn set_source_mac_address(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetSourceMacAddressRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetSourceMacAddress",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetSourceMacAddress"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-                max_decoding_message_size: None,
-                max_encoding_message_size: None,
-            }
+        pub async fn set_ip_options_policy(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetIpOptionsPolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetIpOptionsPolicy",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetIpOptionsPolicy"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
-        {
-            InterceptedService::new(Self::new(inner), interceptor)
+        pub async fn set_udp_zero_checksum_policy(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetUdpZeroChecksumPolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetUdpZeroChecksumPolicy",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "management.RouterService",
+                        "SetUdpZeroChecksumPolicy",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Enable decompressing requests with the given encoding.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
-            self
+        pub async fn set_min_routing_header_len(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetMinRoutingHeaderLenRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetMinRoutingHeaderLen",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetMinRoutingHeaderLen"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Compress responses with the given encoding, if the client supports it.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
-            self
+        pub async fn set_ttl_expired_policy(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetTtlExpiredPolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetTtlExpiredPolicy",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetTtlExpiredPolicy"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.max_decoding_message_size = Some(limit);
-            self
+        pub async fn set_fragment_policy(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetFragmentPolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetFragmentPolicy",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetFragmentPolicy"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.max_encoding_message_size = Some(limit);
-            self
+        pub async fn add_blocklist_entries(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AddBlocklistEntriesRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/AddBlocklistEntries",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "AddBlocklistEntries"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-    }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for RouterServiceServer<T>
-    where
-        T: RouterService,
-        B: Body + std::marker::Send + 'static,
-        B::Error: Into<StdError> + std::marker::Send + 'static,
-    {
-        type Response = http::Response<tonic::body::BoxBody>;
-        type Error = std::convert::Infallible;
-        type Future = BoxFuture<Self::Response, Self::Error>;
-        fn poll_ready(
+        pub async fn remove_blocklist_entries(
             &mut self,
-            _cx: &mut Context<'_>,
-        ) -> Poll<std::result::Result<(), Self::Error>> {
-            Poll::Ready(Ok(()))
+            request: impl tonic::IntoRequest<super::RemoveBlocklistEntriesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RemoveBlocklistEntriesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/RemoveBlocklistEntries",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "RemoveBlocklistEntries"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        fn call(&mut self, req: http::Request<B>) -> Self::Future {
-            match req.uri().path() {
-                "/management.RouterService/GetStats" => {
+        pub async fn list_blocklist_entries(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListBlocklistEntriesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBlocklistEntriesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/ListBlocklistEntries",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "ListBlocklistEntries"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn add_backend_remaps(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AddBackendRemapsRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/AddBackendRemaps",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("management.RouterService", "AddBackendRemaps"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn remove_backend_remaps(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RemoveBackendRemapsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RemoveBackendRemapsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/RemoveBackendRemaps",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "RemoveBackendRemaps"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_backend_remaps(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListBackendRemapsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBackendRemapsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/ListBackendRemaps",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "ListBackendRemaps"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_client_rate_limit(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetClientRateLimitRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetClientRateLimit",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetClientRateLimit"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_prefix_rate_limit(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetPrefixRateLimitRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetPrefixRateLimit",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetPrefixRateLimit"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn remove_prefix_rate_limit(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RemovePrefixRateLimitRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/RemovePrefixRateLimit",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "RemovePrefixRateLimit"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_prefix_rate_limits(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListPrefixRateLimitsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListPrefixRateLimitsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/ListPrefixRateLimits",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "ListPrefixRateLimits"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_top_rate_limited_clients(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetTopRateLimitedClientsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetTopRateLimitedClientsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/GetTopRateLimitedClients",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "management.RouterService",
+                        "GetTopRateLimitedClients",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_backend_rate_limit(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetPrefixRateLimitRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetBackendRateLimit",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetBackendRateLimit"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn remove_backend_rate_limit(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RemovePrefixRateLimitRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/RemoveBackendRateLimit",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "RemoveBackendRateLimit"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_backend_rate_limits(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListPrefixRateLimitsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListPrefixRateLimitsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/ListBackendRateLimits",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "ListBackendRateLimits"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_header_auth_policy(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetHeaderAuthPolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetHeaderAuthPolicy",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetHeaderAuthPolicy"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn rotate_header_key(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RotateKeyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/RotateHeaderKey",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("management.RouterService", "RotateHeaderKey"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn retire_previous_header_key(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RetirePreviousKeyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/RetirePreviousHeaderKey",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "management.RouterService",
+                        "RetirePreviousHeaderKey",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_seen_client_window(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetSeenClientWindowRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetSeenClientWindow",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetSeenClientWindow"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_max_amplification(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetMaxAmplificationRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetMaxAmplification",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetMaxAmplification"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_challenge_policy(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetChallengePolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetChallengePolicy",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetChallengePolicy"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn add_service_backends(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ServiceBackendsRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/AddServiceBackends",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "AddServiceBackends"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn remove_service_backends(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ServiceBackendsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RemoveServiceBackendsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/RemoveServiceBackends",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "RemoveServiceBackends"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_service_canary_percent(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetServiceCanaryPercentRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetServiceCanaryPercent",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "management.RouterService",
+                        "SetServiceCanaryPercent",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_services(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListServicesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListServicesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/ListServices",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("management.RouterService", "ListServices"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_backend_health(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBackendHealthRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBackendHealthResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/GetBackendHealth",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("management.RouterService", "GetBackendHealth"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_backend_state(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetBackendStateRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetBackendState",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("management.RouterService", "SetBackendState"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_flow_idle_timeout(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetFlowIdleTimeoutRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetFlowIdleTimeout",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetFlowIdleTimeout"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_flows(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListFlowsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListFlowsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/ListFlows",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("management.RouterService", "ListFlows"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn flush_flows(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FlushFlowsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::FlushFlowsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/FlushFlows",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("management.RouterService", "FlushFlows"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn pin_flow(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PinFlowRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/PinFlow",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("management.RouterService", "PinFlow"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn unpin_flow(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UnpinFlowRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UnpinFlowResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/UnpinFlow",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("management.RouterService", "UnpinFlow"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_traffic_client_prefix_len(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetTrafficClientPrefixLenRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetTrafficClientPrefixLen",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "management.RouterService",
+                        "SetTrafficClientPrefixLen",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_traffic_counters(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListTrafficCountersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListTrafficCountersResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/ListTrafficCounters",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "ListTrafficCounters"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_top_talkers(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetTopTalkersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetTopTalkersResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/GetTopTalkers",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("management.RouterService", "GetTopTalkers"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_top_talker_blocking(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetTopTalkerBlockingRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetTopTalkerBlocking",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetTopTalkerBlocking"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_backend_states(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListBackendStatesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBackendStatesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/ListBackendStates",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "ListBackendStates"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_client_token_policy(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetClientTokenPolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/SetClientTokenPolicy",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "SetClientTokenPolicy"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn rotate_client_token_key(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RotateKeyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/RotateClientTokenKey",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("management.RouterService", "RotateClientTokenKey"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn retire_previous_client_token_key(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RetirePreviousKeyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/management.RouterService/RetirePreviousClientTokenKey",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "management.RouterService",
+                        "RetirePreviousClientTokenKey",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod router_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with RouterServiceServer.
+    #[async_trait]
+    pub trait RouterService: std::marker::Send + std::marker::Sync + 'static {
+        async fn get_stats(
+            &self,
+            request: tonic::Request<super::GetStatsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetStatsResponse>,
+            tonic::Status,
+        >;
+        async fn set_local_net_and_mask(
+            &self,
+            request: tonic::Request<super::SetLocalNetAndMaskRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_backend_net_and_mask(
+            &self,
+            request: tonic::Request<super::SetBackendNetAndMaskRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_gateway_mac_address(
+            &self,
+            request: tonic::Request<super::SetGatewayMacAddressRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_source_mac_address(
+            &self,
+            request: tonic::Request<super::SetSourceMacAddressRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_ip_options_policy(
+            &self,
+            request: tonic::Request<super::SetIpOptionsPolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_udp_zero_checksum_policy(
+            &self,
+            request: tonic::Request<super::SetUdpZeroChecksumPolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_min_routing_header_len(
+            &self,
+            request: tonic::Request<super::SetMinRoutingHeaderLenRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_ttl_expired_policy(
+            &self,
+            request: tonic::Request<super::SetTtlExpiredPolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_fragment_policy(
+            &self,
+            request: tonic::Request<super::SetFragmentPolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn add_blocklist_entries(
+            &self,
+            request: tonic::Request<super::AddBlocklistEntriesRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn remove_blocklist_entries(
+            &self,
+            request: tonic::Request<super::RemoveBlocklistEntriesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RemoveBlocklistEntriesResponse>,
+            tonic::Status,
+        >;
+        async fn list_blocklist_entries(
+            &self,
+            request: tonic::Request<super::ListBlocklistEntriesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBlocklistEntriesResponse>,
+            tonic::Status,
+        >;
+        async fn add_backend_remaps(
+            &self,
+            request: tonic::Request<super::AddBackendRemapsRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn remove_backend_remaps(
+            &self,
+            request: tonic::Request<super::RemoveBackendRemapsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RemoveBackendRemapsResponse>,
+            tonic::Status,
+        >;
+        async fn list_backend_remaps(
+            &self,
+            request: tonic::Request<super::ListBackendRemapsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBackendRemapsResponse>,
+            tonic::Status,
+        >;
+        async fn set_client_rate_limit(
+            &self,
+            request: tonic::Request<super::SetClientRateLimitRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_prefix_rate_limit(
+            &self,
+            request: tonic::Request<super::SetPrefixRateLimitRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn remove_prefix_rate_limit(
+            &self,
+            request: tonic::Request<super::RemovePrefixRateLimitRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn list_prefix_rate_limits(
+            &self,
+            request: tonic::Request<super::ListPrefixRateLimitsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListPrefixRateLimitsResponse>,
+            tonic::Status,
+        >;
+        async fn get_top_rate_limited_clients(
+            &self,
+            request: tonic::Request<super::GetTopRateLimitedClientsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetTopRateLimitedClientsResponse>,
+            tonic::Status,
+        >;
+        async fn set_backend_rate_limit(
+            &self,
+            request: tonic::Request<super::SetPrefixRateLimitRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn remove_backend_rate_limit(
+            &self,
+            request: tonic::Request<super::RemovePrefixRateLimitRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn list_backend_rate_limits(
+            &self,
+            request: tonic::Request<super::ListPrefixRateLimitsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListPrefixRateLimitsResponse>,
+            tonic::Status,
+        >;
+        async fn set_header_auth_policy(
+            &self,
+            request: tonic::Request<super::SetHeaderAuthPolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn rotate_header_key(
+            &self,
+            request: tonic::Request<super::RotateKeyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn retire_previous_header_key(
+            &self,
+            request: tonic::Request<super::RetirePreviousKeyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_seen_client_window(
+            &self,
+            request: tonic::Request<super::SetSeenClientWindowRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_max_amplification(
+            &self,
+            request: tonic::Request<super::SetMaxAmplificationRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_challenge_policy(
+            &self,
+            request: tonic::Request<super::SetChallengePolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn add_service_backends(
+            &self,
+            request: tonic::Request<super::ServiceBackendsRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn remove_service_backends(
+            &self,
+            request: tonic::Request<super::ServiceBackendsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RemoveServiceBackendsResponse>,
+            tonic::Status,
+        >;
+        async fn set_service_canary_percent(
+            &self,
+            request: tonic::Request<super::SetServiceCanaryPercentRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn list_services(
+            &self,
+            request: tonic::Request<super::ListServicesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListServicesResponse>,
+            tonic::Status,
+        >;
+        async fn get_backend_health(
+            &self,
+            request: tonic::Request<super::GetBackendHealthRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBackendHealthResponse>,
+            tonic::Status,
+        >;
+        async fn set_backend_state(
+            &self,
+            request: tonic::Request<super::SetBackendStateRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn set_flow_idle_timeout(
+            &self,
+            request: tonic::Request<super::SetFlowIdleTimeoutRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn list_flows(
+            &self,
+            request: tonic::Request<super::ListFlowsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListFlowsResponse>,
+            tonic::Status,
+        >;
+        async fn flush_flows(
+            &self,
+            request: tonic::Request<super::FlushFlowsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::FlushFlowsResponse>,
+            tonic::Status,
+        >;
+        async fn pin_flow(
+            &self,
+            request: tonic::Request<super::PinFlowRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn unpin_flow(
+            &self,
+            request: tonic::Request<super::UnpinFlowRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UnpinFlowResponse>,
+            tonic::Status,
+        >;
+        async fn set_traffic_client_prefix_len(
+            &self,
+            request: tonic::Request<super::SetTrafficClientPrefixLenRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn list_traffic_counters(
+            &self,
+            request: tonic::Request<super::ListTrafficCountersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListTrafficCountersResponse>,
+            tonic::Status,
+        >;
+        async fn get_top_talkers(
+            &self,
+            request: tonic::Request<super::GetTopTalkersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetTopTalkersResponse>,
+            tonic::Status,
+        >;
+        async fn set_top_talker_blocking(
+            &self,
+            request: tonic::Request<super::SetTopTalkerBlockingRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn list_backend_states(
+            &self,
+            request: tonic::Request<super::ListBackendStatesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBackendStatesResponse>,
+            tonic::Status,
+        >;
+        async fn set_client_token_policy(
+            &self,
+            request: tonic::Request<super::SetClientTokenPolicyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn rotate_client_token_key(
+            &self,
+            request: tonic::Request<super::RotateKeyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn retire_previous_client_token_key(
+            &self,
+            request: tonic::Request<super::RetirePreviousKeyRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct RouterServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> RouterServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for RouterServiceServer<T>
+    where
+        T: RouterService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/management.RouterService/GetStats" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetStatsSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::GetStatsRequest>
+                    for GetStatsSvc<T> {
+                        type Response = super::GetStatsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetStatsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::get_stats(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetStatsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetLocalNetAndMask" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetLocalNetAndMaskSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetLocalNetAndMaskRequest>
+                    for SetLocalNetAndMaskSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetLocalNetAndMaskRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_local_net_and_mask(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetLocalNetAndMaskSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetBackendNetAndMask" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetBackendNetAndMaskSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetBackendNetAndMaskRequest>
+                    for SetBackendNetAndMaskSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetBackendNetAndMaskRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_backend_net_and_mask(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetBackendNetAndMaskSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetGatewayMacAddress" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetGatewayMacAddressSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetGatewayMacAddressRequest>
+                    for SetGatewayMacAddressSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetGatewayMacAddressRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_gateway_mac_address(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetGatewayMacAddressSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetSourceMacAddress" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetSourceMacAddressSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetSourceMacAddressRequest>
+                    for SetSourceMacAddressSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetSourceMacAddressRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_source_mac_address(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetSourceMacAddressSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetIpOptionsPolicy" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetIpOptionsPolicySvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetIpOptionsPolicyRequest>
+                    for SetIpOptionsPolicySvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetIpOptionsPolicyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_ip_options_policy(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetIpOptionsPolicySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetUdpZeroChecksumPolicy" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetUdpZeroChecksumPolicySvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetUdpZeroChecksumPolicyRequest>
+                    for SetUdpZeroChecksumPolicySvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::SetUdpZeroChecksumPolicyRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_udp_zero_checksum_policy(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetUdpZeroChecksumPolicySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetMinRoutingHeaderLen" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetMinRoutingHeaderLenSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetMinRoutingHeaderLenRequest>
+                    for SetMinRoutingHeaderLenSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetMinRoutingHeaderLenRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_min_routing_header_len(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetMinRoutingHeaderLenSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetTtlExpiredPolicy" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetTtlExpiredPolicySvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetTtlExpiredPolicyRequest>
+                    for SetTtlExpiredPolicySvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetTtlExpiredPolicyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_ttl_expired_policy(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetTtlExpiredPolicySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetFragmentPolicy" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetFragmentPolicySvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetFragmentPolicyRequest>
+                    for SetFragmentPolicySvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetFragmentPolicyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_fragment_policy(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetFragmentPolicySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/AddBlocklistEntries" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddBlocklistEntriesSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::AddBlocklistEntriesRequest>
+                    for AddBlocklistEntriesSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddBlocklistEntriesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::add_blocklist_entries(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = AddBlocklistEntriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/RemoveBlocklistEntries" => {
+                    #[allow(non_camel_case_types)]
+                    struct RemoveBlocklistEntriesSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::RemoveBlocklistEntriesRequest>
+                    for RemoveBlocklistEntriesSvc<T> {
+                        type Response = super::RemoveBlocklistEntriesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RemoveBlocklistEntriesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::remove_blocklist_entries(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RemoveBlocklistEntriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/ListBlocklistEntries" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListBlocklistEntriesSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::ListBlocklistEntriesRequest>
+                    for ListBlocklistEntriesSvc<T> {
+                        type Response = super::ListBlocklistEntriesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListBlocklistEntriesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::list_blocklist_entries(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListBlocklistEntriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/AddBackendRemaps" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddBackendRemapsSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::AddBackendRemapsRequest>
+                    for AddBackendRemapsSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddBackendRemapsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::add_backend_remaps(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = AddBackendRemapsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/RemoveBackendRemaps" => {
+                    #[allow(non_camel_case_types)]
+                    struct RemoveBackendRemapsSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::RemoveBackendRemapsRequest>
+                    for RemoveBackendRemapsSvc<T> {
+                        type Response = super::RemoveBackendRemapsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RemoveBackendRemapsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::remove_backend_remaps(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RemoveBackendRemapsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/ListBackendRemaps" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListBackendRemapsSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::ListBackendRemapsRequest>
+                    for ListBackendRemapsSvc<T> {
+                        type Response = super::ListBackendRemapsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListBackendRemapsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::list_backend_remaps(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListBackendRemapsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetClientRateLimit" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetClientRateLimitSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetClientRateLimitRequest>
+                    for SetClientRateLimitSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetClientRateLimitRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_client_rate_limit(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetClientRateLimitSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetPrefixRateLimit" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetPrefixRateLimitSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetPrefixRateLimitRequest>
+                    for SetPrefixRateLimitSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetPrefixRateLimitRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_prefix_rate_limit(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetPrefixRateLimitSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/RemovePrefixRateLimit" => {
+                    #[allow(non_camel_case_types)]
+                    struct RemovePrefixRateLimitSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::RemovePrefixRateLimitRequest>
+                    for RemovePrefixRateLimitSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RemovePrefixRateLimitRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::remove_prefix_rate_limit(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RemovePrefixRateLimitSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/ListPrefixRateLimits" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListPrefixRateLimitsSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::ListPrefixRateLimitsRequest>
+                    for ListPrefixRateLimitsSvc<T> {
+                        type Response = super::ListPrefixRateLimitsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListPrefixRateLimitsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::list_prefix_rate_limits(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListPrefixRateLimitsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/GetTopRateLimitedClients" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetTopRateLimitedClientsSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::GetTopRateLimitedClientsRequest>
+                    for GetTopRateLimitedClientsSvc<T> {
+                        type Response = super::GetTopRateLimitedClientsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::GetTopRateLimitedClientsRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::get_top_rate_limited_clients(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetTopRateLimitedClientsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetBackendRateLimit" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetBackendRateLimitSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetPrefixRateLimitRequest>
+                    for SetBackendRateLimitSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetPrefixRateLimitRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_backend_rate_limit(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetBackendRateLimitSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/RemoveBackendRateLimit" => {
+                    #[allow(non_camel_case_types)]
+                    struct RemoveBackendRateLimitSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::RemovePrefixRateLimitRequest>
+                    for RemoveBackendRateLimitSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RemovePrefixRateLimitRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::remove_backend_rate_limit(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RemoveBackendRateLimitSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/ListBackendRateLimits" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListBackendRateLimitsSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::ListPrefixRateLimitsRequest>
+                    for ListBackendRateLimitsSvc<T> {
+                        type Response = super::ListPrefixRateLimitsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListPrefixRateLimitsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::list_backend_rate_limits(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListBackendRateLimitsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetHeaderAuthPolicy" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetHeaderAuthPolicySvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetHeaderAuthPolicyRequest>
+                    for SetHeaderAuthPolicySvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetHeaderAuthPolicyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_header_auth_policy(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetHeaderAuthPolicySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/RotateHeaderKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct RotateHeaderKeySvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::RotateKeyRequest>
+                    for RotateHeaderKeySvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RotateKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::rotate_header_key(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RotateHeaderKeySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/RetirePreviousHeaderKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct RetirePreviousHeaderKeySvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::RetirePreviousKeyRequest>
+                    for RetirePreviousHeaderKeySvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RetirePreviousKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::retire_previous_header_key(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RetirePreviousHeaderKeySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetSeenClientWindow" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetSeenClientWindowSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetSeenClientWindowRequest>
+                    for SetSeenClientWindowSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetSeenClientWindowRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_seen_client_window(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetSeenClientWindowSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetMaxAmplification" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetMaxAmplificationSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetMaxAmplificationRequest>
+                    for SetMaxAmplificationSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetMaxAmplificationRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_max_amplification(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetMaxAmplificationSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetChallengePolicy" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetChallengePolicySvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetChallengePolicyRequest>
+                    for SetChallengePolicySvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetChallengePolicyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_challenge_policy(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetChallengePolicySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/AddServiceBackends" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddServiceBackendsSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::ServiceBackendsRequest>
+                    for AddServiceBackendsSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ServiceBackendsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::add_service_backends(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = AddServiceBackendsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/RemoveServiceBackends" => {
+                    #[allow(non_camel_case_types)]
+                    struct RemoveServiceBackendsSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::ServiceBackendsRequest>
+                    for RemoveServiceBackendsSvc<T> {
+                        type Response = super::RemoveServiceBackendsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ServiceBackendsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::remove_service_backends(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RemoveServiceBackendsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetServiceCanaryPercent" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetServiceCanaryPercentSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetServiceCanaryPercentRequest>
+                    for SetServiceCanaryPercentSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::SetServiceCanaryPercentRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_service_canary_percent(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetServiceCanaryPercentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/ListServices" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListServicesSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::ListServicesRequest>
+                    for ListServicesSvc<T> {
+                        type Response = super::ListServicesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListServicesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::list_services(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListServicesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/GetBackendHealth" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBackendHealthSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::GetBackendHealthRequest>
+                    for GetBackendHealthSvc<T> {
+                        type Response = super::GetBackendHealthResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBackendHealthRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::get_backend_health(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetBackendHealthSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetBackendState" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetBackendStateSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetBackendStateRequest>
+                    for SetBackendStateSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetBackendStateRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_backend_state(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetBackendStateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetFlowIdleTimeout" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetFlowIdleTimeoutSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetFlowIdleTimeoutRequest>
+                    for SetFlowIdleTimeoutSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetFlowIdleTimeoutRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::set_flow_idle_timeout(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetFlowIdleTimeoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/ListFlows" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListFlowsSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::ListFlowsRequest>
+                    for ListFlowsSvc<T> {
+                        type Response = super::ListFlowsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListFlowsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::list_flows(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListFlowsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/FlushFlows" => {
+                    #[allow(non_camel_case_types)]
+                    struct FlushFlowsSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::FlushFlowsRequest>
+                    for FlushFlowsSvc<T> {
+                        type Response = super::FlushFlowsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FlushFlowsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::flush_flows(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FlushFlowsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/PinFlow" => {
+                    #[allow(non_camel_case_types)]
+                    struct PinFlowSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::PinFlowRequest>
+                    for PinFlowSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PinFlowRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::pin_flow(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PinFlowSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/UnpinFlow" => {
                     #[allow(non_camel_case_types)]
-                    struct GetStatsSvc<T: RouterService>(pub Arc<T>);
+                    struct UnpinFlowSvc<T: RouterService>(pub Arc<T>);
                     impl<
                         T: RouterService,
-                    > tonic::server::UnaryService<super::GetStatsRequest>
-                    for GetStatsSvc<T> {
-                        type Response = super::GetStatsResponse;
+                    > tonic::server::UnaryService<super::UnpinFlowRequest>
+                    for UnpinFlowSvc<T> {
+                        type Response = super::UnpinFlowResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetStatsRequest>,
+                            request: tonic::Request<super::UnpinFlowRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as RouterService>::get_stats(&inner, request).await
+                                <T as RouterService>::unpin_flow(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -353,7 +4377,7 @@ pub mod router_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetStatsSvc(inner);
+                        let method = UnpinFlowSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -369,13 +4393,14 @@ pub mod router_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/management.RouterService/SetLocalNetAndMask" => {
+                "/management.RouterService/SetTrafficClientPrefixLen" => {
                     #[allow(non_camel_case_types)]
-                    struct SetLocalNetAndMaskSvc<T: RouterService>(pub Arc<T>);
+                    struct SetTrafficClientPrefixLenSvc<T: RouterService>(pub Arc<T>);
                     impl<
                         T: RouterService,
-                    > tonic::server::UnaryService<super::SetLocalNetAndMaskRequest>
-                    for SetLocalNetAndMaskSvc<T> {
+                    > tonic::server::UnaryService<
+                        super::SetTrafficClientPrefixLenRequest,
+                    > for SetTrafficClientPrefixLenSvc<T> {
                         type Response = ();
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -383,11 +4408,13 @@ pub mod router_service_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SetLocalNetAndMaskRequest>,
+                            request: tonic::Request<
+                                super::SetTrafficClientPrefixLenRequest,
+                            >,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as RouterService>::set_local_net_and_mask(
+                                <T as RouterService>::set_traffic_client_prefix_len(
                                         &inner,
                                         request,
                                     )
@@ -402,7 +4429,7 @@ pub mod router_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SetLocalNetAndMaskSvc(inner);
+                        let method = SetTrafficClientPrefixLenSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -418,13 +4445,104 @@ pub mod router_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/management.RouterService/SetBackendNetAndMask" => {
+                "/management.RouterService/ListTrafficCounters" => {
                     #[allow(non_camel_case_types)]
-                    struct SetBackendNetAndMaskSvc<T: RouterService>(pub Arc<T>);
+                    struct ListTrafficCountersSvc<T: RouterService>(pub Arc<T>);
                     impl<
                         T: RouterService,
-                    > tonic::server::UnaryService<super::SetBackendNetAndMaskRequest>
-                    for SetBackendNetAndMaskSvc<T> {
+                    > tonic::server::UnaryService<super::ListTrafficCountersRequest>
+                    for ListTrafficCountersSvc<T> {
+                        type Response = super::ListTrafficCountersResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListTrafficCountersRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::list_traffic_counters(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListTrafficCountersSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/GetTopTalkers" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetTopTalkersSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::GetTopTalkersRequest>
+                    for GetTopTalkersSvc<T> {
+                        type Response = super::GetTopTalkersResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetTopTalkersRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::get_top_talkers(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetTopTalkersSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetTopTalkerBlocking" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetTopTalkerBlockingSvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetTopTalkerBlockingRequest>
+                    for SetTopTalkerBlockingSvc<T> {
                         type Response = ();
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -432,11 +4550,11 @@ pub mod router_service_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SetBackendNetAndMaskRequest>,
+                            request: tonic::Request<super::SetTopTalkerBlockingRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as RouterService>::set_backend_net_and_mask(
+                                <T as RouterService>::set_top_talker_blocking(
                                         &inner,
                                         request,
                                     )
@@ -451,7 +4569,7 @@ pub mod router_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SetBackendNetAndMaskSvc(inner);
+                        let method = SetTopTalkerBlockingSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -467,13 +4585,59 @@ pub mod router_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/management.RouterService/SetGatewayMacAddress" => {
+                "/management.RouterService/ListBackendStates" => {
                     #[allow(non_camel_case_types)]
-                    struct SetGatewayMacAddressSvc<T: RouterService>(pub Arc<T>);
+                    struct ListBackendStatesSvc<T: RouterService>(pub Arc<T>);
                     impl<
                         T: RouterService,
-                    > tonic::server::UnaryService<super::SetGatewayMacAddressRequest>
-                    for SetGatewayMacAddressSvc<T> {
+                    > tonic::server::UnaryService<super::ListBackendStatesRequest>
+                    for ListBackendStatesSvc<T> {
+                        type Response = super::ListBackendStatesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListBackendStatesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::list_backend_states(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListBackendStatesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/SetClientTokenPolicy" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetClientTokenPolicySvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::SetClientTokenPolicyRequest>
+                    for SetClientTokenPolicySvc<T> {
                         type Response = ();
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -481,11 +4645,11 @@ pub mod router_service_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SetGatewayMacAddressRequest>,
+                            request: tonic::Request<super::SetClientTokenPolicyRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as RouterService>::set_gateway_mac_address(
+                                <T as RouterService>::set_client_token_policy(
                                         &inner,
                                         request,
                                     )
@@ -500,7 +4664,105 @@ pub mod router_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SetGatewayMacAddressSvc(inner);
+                        let method = SetClientTokenPolicySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/RotateClientTokenKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct RotateClientTokenKeySvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::RotateKeyRequest>
+                    for RotateClientTokenKeySvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RotateKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::rotate_client_token_key(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RotateClientTokenKeySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/management.RouterService/RetirePreviousClientTokenKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct RetirePreviousClientTokenKeySvc<T: RouterService>(pub Arc<T>);
+                    impl<
+                        T: RouterService,
+                    > tonic::server::UnaryService<super::RetirePreviousKeyRequest>
+                    for RetirePreviousClientTokenKeySvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RetirePreviousKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RouterService>::retire_previous_client_token_key(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RetirePreviousClientTokenKeySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
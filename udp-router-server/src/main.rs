@@ -1,5 +1,6 @@
 use tokio::io;
 use tokio::net::UdpSocket;
+use udp_router_common::health;
 
 #[tokio::main]
 async fn main() -> io::Result<()> {
@@ -13,6 +14,11 @@ async fn main() -> io::Result<()> {
         // Receive a message
         let (len, addr) = socket.recv_from(&mut buf).await?;
         let received = &buf[..len];
-        socket.send_to(received, &addr).await?;
+
+        // Health check probes of the router are answered instead of echoed
+        match health::answer(received) {
+            Some(reply) => socket.send_to(&reply, &addr).await?,
+            None => socket.send_to(received, &addr).await?,
+        };
     }
 }
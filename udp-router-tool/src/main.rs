@@ -1,12 +1,32 @@
-use clap::Parser;
-use std::net::Ipv4Addr;
+use clap::{Parser, Subcommand};
+use std::fs;
+use std::net::{Ipv4Addr, SocketAddrV4};
 use tonic::Request;
+use udp_router_common::siphash::parse_key;
 use udp_router_protobuf::management::router_service_client::RouterServiceClient;
 use udp_router_protobuf::management::{
-    GetStatsRequest, SetBackendNetAndMaskRequest, SetGatewayMacAddressRequest,
-    SetLocalNetAndMaskRequest,
+    AddBackendRemapsRequest, AddBlocklistEntriesRequest, BackendRemap, BackendState,
+    BlocklistEntry, ChallengePolicy, ClientTokenPolicy, FlushFlowsRequest, FragmentPolicy,
+    GetBackendHealthRequest, GetStatsRequest, GetTopRateLimitedClientsRequest,
+    GetTopTalkersRequest, HeaderAuthPolicy, InterfaceSide, IpOptionsPolicy,
+    ListBackendRemapsRequest, ListBackendStatesRequest, ListBlocklistEntriesRequest,
+    ListFlowsRequest, ListPrefixRateLimitsRequest, ListServicesRequest, ListTrafficCountersRequest,
+    PinFlowRequest, PrefixRateLimit, RemoveBackendRemapsRequest, RemoveBlocklistEntriesRequest,
+    RemovePrefixRateLimitRequest, RetirePreviousKeyRequest, RotateKeyRequest,
+    ServiceBackendsRequest, SetBackendNetAndMaskRequest, SetBackendStateRequest,
+    SetChallengePolicyRequest, SetClientRateLimitRequest, SetClientTokenPolicyRequest,
+    SetFlowIdleTimeoutRequest, SetFragmentPolicyRequest, SetGatewayMacAddressRequest,
+    SetHeaderAuthPolicyRequest, SetIpOptionsPolicyRequest, SetLocalNetAndMaskRequest,
+    SetMaxAmplificationRequest, SetMinRoutingHeaderLenRequest, SetPrefixRateLimitRequest,
+    SetSeenClientWindowRequest, SetServiceCanaryPercentRequest, SetSourceMacAddressRequest,
+    SetTopTalkerBlockingRequest, SetTrafficClientPrefixLenRequest, SetTtlExpiredPolicyRequest,
+    SetUdpZeroChecksumPolicyRequest, TrafficKeyed, TtlExpiredPolicy, UdpZeroChecksumPolicy,
+    UnpinFlowRequest,
 };
 
+// Number of traffic counters requested at a time
+const TRAFFIC_PAGE_SIZE: u32 = 1000;
+
 //
 // Command line parsing
 //
@@ -31,6 +51,189 @@ struct Opt {
     /// Set gateway MAC address (e.g. 00:11:22:33:44:55)
     #[clap(long)]
     set_gateway_mac_address: String,
+    /// Set gateway MAC address of backend-facing interface (e.g. 00:11:22:33:44:55)
+    #[clap(long, default_value = "")]
+    set_backend_gateway_mac_address: String,
+    /// Set source MAC address of forwarded packets (e.g. 00:11:22:33:44:55)
+    #[clap(long, default_value = "")]
+    set_source_mac_address: String,
+    /// Set source MAC address of packets forwarded to backends (e.g. 00:11:22:33:44:55)
+    #[clap(long, default_value = "")]
+    set_backend_source_mac_address: String,
+    /// Set handling of packets with IPv4 header options (forward or drop)
+    #[clap(long, default_value = "")]
+    set_ip_options_policy: String,
+    /// Set handling of UDP packets without checksum (keep or calculate)
+    #[clap(long, default_value = "")]
+    set_udp_zero_checksum_policy: String,
+    /// Set minimum length of the routing header in the UDP payload in bytes
+    #[clap(long)]
+    set_min_routing_header_len: Option<u32>,
+    /// Set handling of packets whose TTL expired (drop or icmp)
+    #[clap(long, default_value = "")]
+    set_ttl_expired_policy: String,
+    /// Set handling of IPv4 fragments (drop, pass or forward-first)
+    #[clap(long, default_value = "")]
+    set_fragment_policy: String,
+    /// Add client prefixes listed in a file to the blocklist. Each line holds a prefix
+    /// (e.g. 192.0.2.0/24) and optionally the number of seconds until it expires.
+    #[clap(long, default_value = "")]
+    blocklist_import: String,
+    /// Remove client prefixes listed in a file from the blocklist
+    #[clap(long, default_value = "")]
+    blocklist_remove: String,
+    /// Send packets to the old address of a backend to a new address, with optional expiry
+    /// in seconds (e.g. 10.0.3.10=10.0.3.20,3600)
+    #[clap(long, default_value = "")]
+    add_backend_remap: String,
+    /// Stop sending packets to the old address of a backend elsewhere
+    #[clap(long, default_value = "")]
+    remove_backend_remap: String,
+    /// Print remapped backends with the number of packets sent to each new address
+    #[clap(long, default_value_t = false)]
+    list_backend_remaps: bool,
+    /// Print the blocklist with the number of packets dropped by each prefix
+    #[clap(long, default_value_t = false)]
+    blocklist_list: bool,
+    /// Set packets per second and optionally burst allowed from each client (e.g. 1000,2000)
+    #[clap(long, default_value = "")]
+    set_client_rate_limit: String,
+    /// Set rate limit of clients in a prefix (e.g. 192.0.2.0/24,1000,2000)
+    #[clap(long, default_value = "")]
+    set_prefix_rate_limit: String,
+    /// Remove rate limit of clients in a prefix (e.g. 192.0.2.0/24)
+    #[clap(long, default_value = "")]
+    remove_prefix_rate_limit: String,
+    /// Print rate limits of client prefixes
+    #[clap(long, default_value_t = false)]
+    list_prefix_rate_limits: bool,
+    /// Print the given number of clients with the most packets dropped by rate limiting
+    #[clap(long)]
+    top_rate_limited_clients: Option<u32>,
+    /// Set rate limit of backends in a prefix (e.g. 198.51.100.7/32,100000,200000)
+    #[clap(long, default_value = "")]
+    set_backend_rate_limit: String,
+    /// Remove rate limit of backends in a prefix (e.g. 198.51.100.7/32)
+    #[clap(long, default_value = "")]
+    remove_backend_rate_limit: String,
+    /// Print rate limits of backend prefixes
+    #[clap(long, default_value_t = false)]
+    list_backend_rate_limits: bool,
+    /// Add backends to the pool of a service (e.g. 7=10.0.3.10,10.0.3.11)
+    #[clap(long, default_value = "")]
+    add_service_backends: String,
+    /// Remove backends from the pool of a service (e.g. 7=10.0.3.10)
+    #[clap(long, default_value = "")]
+    remove_service_backends: String,
+    /// Add canary backends to a service (e.g. 7=10.0.3.20)
+    #[clap(long, default_value = "")]
+    add_service_canaries: String,
+    /// Remove canary backends from a service (e.g. 7=10.0.3.20)
+    #[clap(long, default_value = "")]
+    remove_service_canaries: String,
+    /// Set the percentage of clients of a service sent to its canaries (e.g. 7=10)
+    #[clap(long, default_value = "")]
+    set_service_canary_percent: String,
+    /// Print the backend pools of services
+    #[clap(long, default_value_t = false)]
+    list_services: bool,
+    /// Forget the backends of client flows idle for the given number of seconds, or 60 if
+    /// zero
+    #[clap(long)]
+    set_flow_idle_timeout: Option<u32>,
+    /// Print the given number of client flows and their backends, or all if zero
+    #[clap(long)]
+    list_flows: Option<u32>,
+    /// Make flows sent to a backend, or to any backend if "all", forget their backend
+    #[clap(long, default_value = "")]
+    flush_flows: String,
+    /// Keep a client flow with a backend of a service (e.g. 192.0.2.1:4000,7,10.0.3.10)
+    #[clap(long, default_value = "")]
+    pin_flow: String,
+    /// Let a pinned client flow move between backends again (e.g. 192.0.2.1:4000)
+    #[clap(long, default_value = "")]
+    unpin_flow: String,
+    /// Count traffic by client prefix of the given length as well as by backend, or only by
+    /// backend if zero
+    #[clap(long)]
+    set_traffic_client_prefix_len: Option<u32>,
+    /// Print the traffic forwarded to and from each backend or client prefix (backends,
+    /// clients)
+    #[clap(long, default_value = "")]
+    list_traffic: String,
+    /// Block clients sending more packets per second than the rate for the given number of
+    /// seconds (e.g. 50000,300), or stop blocking if "0"
+    #[clap(long, default_value = "")]
+    block_top_talkers: String,
+    /// Print the health of the backends of services
+    #[clap(long, default_value_t = false)]
+    backend_health: bool,
+    /// Set routing header authentication policy (disabled, required)
+    #[clap(long, default_value = "")]
+    set_header_auth_policy: String,
+    /// Make a key of 32 hex digits the current routing header key, keeping the old key
+    /// accepted until retired
+    #[clap(long, default_value = "")]
+    rotate_header_key: String,
+    /// Stop accepting routing headers tagged with the previous key
+    #[clap(long, default_value_t = false)]
+    retire_previous_header_key: bool,
+    /// Only forward responses to clients seen within the given number of seconds, or to
+    /// any client if zero
+    #[clap(long)]
+    set_seen_client_window: Option<u32>,
+    /// Drop responses to a client beyond the given multiple of its request bytes, or
+    /// never if zero
+    #[clap(long)]
+    set_max_amplification: Option<u32>,
+    /// Set whether clients must echo a challenge cookie (disabled, required)
+    #[clap(long, default_value = "")]
+    set_challenge_policy: String,
+    /// Set whether backends see clients by token instead of address (disabled, enabled)
+    #[clap(long, default_value = "")]
+    set_client_token_policy: String,
+    /// Make a key of 32 hex digits the current client token key, keeping the old key
+    /// accepted until retired
+    #[clap(long, default_value = "")]
+    rotate_client_token_key: String,
+    /// Stop accepting client tokens made with the previous key
+    #[clap(long, default_value_t = false)]
+    retire_previous_client_token_key: bool,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+enum Command {
+    /// Take backends out of services for maintenance
+    Backend {
+        #[clap(subcommand)]
+        action: BackendAction,
+    },
+    /// Print the clients and backends with the most packets in the last window
+    TopTalkers {
+        /// Number of clients and backends to print
+        #[clap(long, default_value_t = 10)]
+        limit: u32,
+    },
+}
+
+#[derive(Clone, Debug, Subcommand)]
+enum BackendAction {
+    /// Only send packets of the clients a backend serves to it, until disabled after the
+    /// timeout
+    Drain {
+        addr: Ipv4Addr,
+        /// Seconds until the backend is disabled
+        #[clap(long, default_value_t = 300)]
+        timeout: u32,
+    },
+    /// Send packets through services to a backend again
+    Enable { addr: Ipv4Addr },
+    /// Send no packets through services to a backend
+    Disable { addr: Ipv4Addr },
+    /// Print the backends that are draining or disabled
+    List,
 }
 
 #[tokio::main]
@@ -52,6 +255,100 @@ async fn main() -> Result<(), anyhow::Error> {
                 println!("total_packets: {}", res.total_packets);
                 println!("client_to_server_packets: {}", res.client_to_server_packets);
                 println!("server_to_client_packets: {}", res.server_to_client_packets);
+                println!(
+                    "non_unicast_dropped_packets: {}",
+                    res.non_unicast_dropped_packets
+                );
+                println!(
+                    "ip_options_dropped_packets: {}",
+                    res.ip_options_dropped_packets
+                );
+                println!(
+                    "udp_checksum_added_packets: {}",
+                    res.udp_checksum_added_packets
+                );
+                println!("ip_length_dropped_packets: {}", res.ip_length_dropped_packets);
+                println!(
+                    "udp_length_dropped_packets: {}",
+                    res.udp_length_dropped_packets
+                );
+                println!(
+                    "short_routing_header_dropped_packets: {}",
+                    res.short_routing_header_dropped_packets
+                );
+                println!(
+                    "ttl_expired_dropped_packets: {}",
+                    res.ttl_expired_dropped_packets
+                );
+                println!(
+                    "icmp_time_exceeded_sent_packets: {}",
+                    res.icmp_time_exceeded_sent_packets
+                );
+                println!(
+                    "fragments_dropped_packets: {}",
+                    res.fragments_dropped_packets
+                );
+                println!("fragments_passed_packets: {}", res.fragments_passed_packets);
+                println!(
+                    "first_fragments_forwarded_packets: {}",
+                    res.first_fragments_forwarded_packets
+                );
+                println!(
+                    "target_unspecified_dropped_packets: {}",
+                    res.target_unspecified_dropped_packets
+                );
+                println!(
+                    "target_loopback_dropped_packets: {}",
+                    res.target_loopback_dropped_packets
+                );
+                println!(
+                    "target_multicast_dropped_packets: {}",
+                    res.target_multicast_dropped_packets
+                );
+                println!(
+                    "target_broadcast_dropped_packets: {}",
+                    res.target_broadcast_dropped_packets
+                );
+                println!(
+                    "target_link_local_dropped_packets: {}",
+                    res.target_link_local_dropped_packets
+                );
+                println!(
+                    "target_local_network_dropped_packets: {}",
+                    res.target_local_network_dropped_packets
+                );
+                println!("target_router_dropped_packets: {}", res.target_router_dropped_packets);
+                println!("rate_limited_dropped_packets: {}", res.rate_limited_dropped_packets);
+                println!("blocklist_dropped_packets: {}", res.blocklist_dropped_packets);
+                println!(
+                    "backend_overload_dropped_packets: {}",
+                    res.backend_overload_dropped_packets
+                );
+                println!("header_auth_dropped_packets: {}", res.header_auth_dropped_packets);
+                println!("client_token_dropped_packets: {}", res.client_token_dropped_packets);
+                println!("unseen_client_dropped_packets: {}", res.unseen_client_dropped_packets);
+                println!("amplification_dropped_packets: {}", res.amplification_dropped_packets);
+                println!("challenges_sent_packets: {}", res.challenges_sent_packets);
+                println!("cookies_verified_packets: {}", res.cookies_verified_packets);
+                println!("cookie_dropped_packets: {}", res.cookie_dropped_packets);
+                println!(
+                    "service_unavailable_dropped_packets: {}",
+                    res.service_unavailable_dropped_packets
+                );
+                println!(
+                    "unavailable_backend_skipped_packets: {}",
+                    res.unavailable_backend_skipped_packets
+                );
+                println!("backend_remapped_packets: {}", res.backend_remapped_packets);
+                println!("unique_clients: {}", res.unique_clients);
+                for backend in res.backends {
+                    println!(
+                        "backend {} shed_packets: {} service_packets: {}",
+                        Ipv4Addr::from_bits(backend.addr),
+                        backend.shed_packets,
+                        backend.service_packets
+                    );
+                }
                 return Ok(());
             }
             Err(e) => {
@@ -92,7 +389,10 @@ async fn main() -> Result<(), anyhow::Error> {
 
     if !opt.set_gateway_mac_address.is_empty() {
         match client
-            .set_gateway_mac_address(Request::new(SetGatewayMacAddressRequest { mac }))
+            .set_gateway_mac_address(Request::new(SetGatewayMacAddressRequest {
+                mac,
+                side: InterfaceSide::Client.into(),
+            }))
             .await
         {
             Ok(_) => (),
@@ -102,36 +402,1053 @@ async fn main() -> Result<(), anyhow::Error> {
         }
     }
 
-    Ok(())
-}
+    if !opt.set_backend_gateway_mac_address.is_empty() {
+        match client
+            .set_gateway_mac_address(Request::new(SetGatewayMacAddressRequest {
+                mac: parse_mac_address(&opt.set_backend_gateway_mac_address),
+                side: InterfaceSide::Backend.into(),
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
 
-fn parse_ip_mask(s: &str) -> (u32, u32) {
-    if s.is_empty() {
-        return (0, 0xffffffff);
+    if !opt.set_source_mac_address.is_empty() {
+        match client
+            .set_source_mac_address(Request::new(SetSourceMacAddressRequest {
+                mac: parse_mac_address(&opt.set_source_mac_address),
+                side: InterfaceSide::Client.into(),
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
     }
 
-    let parts: Vec<&str> = s.split('/').collect();
-    if parts.len() != 2 {
-        panic!("Invalid IP/mask:  {}", s);
+    if !opt.set_backend_source_mac_address.is_empty() {
+        match client
+            .set_source_mac_address(Request::new(SetSourceMacAddressRequest {
+                mac: parse_mac_address(&opt.set_backend_source_mac_address),
+                side: InterfaceSide::Backend.into(),
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
     }
 
-    let net = parts[0].parse::<Ipv4Addr>().unwrap().to_bits();
-    let mask = 0xffffffff_u32 << (32 - parts[1].parse::<u32>().unwrap());
+    if !opt.set_ip_options_policy.is_empty() {
+        match client
+            .set_ip_options_policy(Request::new(SetIpOptionsPolicyRequest {
+                policy: parse_ip_options_policy(&opt.set_ip_options_policy).into(),
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
 
-    (net, mask)
-}
+    if !opt.set_udp_zero_checksum_policy.is_empty() {
+        match client
+            .set_udp_zero_checksum_policy(Request::new(SetUdpZeroChecksumPolicyRequest {
+                policy: parse_udp_zero_checksum_policy(&opt.set_udp_zero_checksum_policy).into(),
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
 
-fn parse_mac_address(s: &str) -> u64 {
-    let parts: Vec<&str> = s.split(':').collect();
-    if parts.len() != 6 {
-        panic!("Invalid MAC address:  {}", s);
+    if let Some(len) = opt.set_min_routing_header_len {
+        match client
+            .set_min_routing_header_len(Request::new(SetMinRoutingHeaderLenRequest { len }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
     }
 
-    let mut res = 0;
-    for part in parts.iter() {
-        let byte = u8::from_str_radix(part, 16).unwrap();
-        res = (res << 8) | byte as u64;
+    if !opt.set_ttl_expired_policy.is_empty() {
+        match client
+            .set_ttl_expired_policy(Request::new(SetTtlExpiredPolicyRequest {
+                policy: parse_ttl_expired_policy(&opt.set_ttl_expired_policy).into(),
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
     }
 
-    res
+    if !opt.set_fragment_policy.is_empty() {
+        match client
+            .set_fragment_policy(Request::new(SetFragmentPolicyRequest {
+                policy: parse_fragment_policy(&opt.set_fragment_policy).into(),
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.blocklist_import.is_empty() {
+        let entries = read_blocklist_file(&opt.blocklist_import);
+        let count = entries.len();
+        match client
+            .add_blocklist_entries(Request::new(AddBlocklistEntriesRequest { entries }))
+            .await
+        {
+            Ok(_) => println!("Added {} prefixes to blocklist", count),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.blocklist_remove.is_empty() {
+        let entries = read_blocklist_file(&opt.blocklist_remove);
+        match client
+            .remove_blocklist_entries(Request::new(RemoveBlocklistEntriesRequest { entries }))
+            .await
+        {
+            Ok(res) => println!("Removed {} prefixes from blocklist", res.into_inner().removed),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if opt.blocklist_list {
+        match client
+            .list_blocklist_entries(Request::new(ListBlocklistEntriesRequest {}))
+            .await
+        {
+            Ok(res) => {
+                for entry in res.into_inner().entries {
+                    let expires = match entry.expires_in_secs {
+                        0 => "never".to_string(),
+                        secs => format!("{}s", secs),
+                    };
+                    println!(
+                        "{}/{} expires: {} hits: {}",
+                        Ipv4Addr::from_bits(entry.net),
+                        entry.prefix_len,
+                        expires,
+                        entry.hits
+                    );
+                }
+            }
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.add_backend_remap.is_empty() {
+        let remaps = vec![parse_backend_remap(&opt.add_backend_remap)];
+        match client
+            .add_backend_remaps(Request::new(AddBackendRemapsRequest { remaps }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.remove_backend_remap.is_empty() {
+        let old_backends = vec![opt
+            .remove_backend_remap
+            .parse::<Ipv4Addr>()
+            .unwrap()
+            .to_bits()];
+        match client
+            .remove_backend_remaps(Request::new(RemoveBackendRemapsRequest { old_backends }))
+            .await
+        {
+            Ok(res) => println!("Removed {} backend remaps", res.into_inner().removed),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if opt.list_backend_remaps {
+        match client
+            .list_backend_remaps(Request::new(ListBackendRemapsRequest {}))
+            .await
+        {
+            Ok(res) => {
+                for remap in res.into_inner().remaps {
+                    let expires = match remap.expires_in_secs {
+                        0 => "never".to_string(),
+                        secs => format!("{}s", secs),
+                    };
+                    println!(
+                        "{} -> {} expires: {} hits: {}",
+                        Ipv4Addr::from_bits(remap.old_backend),
+                        Ipv4Addr::from_bits(remap.new_backend),
+                        expires,
+                        remap.hits
+                    );
+                }
+            }
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.set_client_rate_limit.is_empty() {
+        let (rate, burst) = parse_rate_limit(&opt.set_client_rate_limit);
+        match client
+            .set_client_rate_limit(Request::new(SetClientRateLimitRequest { rate, burst }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.set_prefix_rate_limit.is_empty() {
+        let limit = parse_prefix_rate_limit(&opt.set_prefix_rate_limit);
+        match client
+            .set_prefix_rate_limit(Request::new(SetPrefixRateLimitRequest { limit: Some(limit) }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.remove_prefix_rate_limit.is_empty() {
+        let (net, prefix_len) = parse_prefix(&opt.remove_prefix_rate_limit);
+        match client
+            .remove_prefix_rate_limit(Request::new(RemovePrefixRateLimitRequest {
+                net,
+                prefix_len,
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if opt.list_prefix_rate_limits {
+        match client
+            .list_prefix_rate_limits(Request::new(ListPrefixRateLimitsRequest {}))
+            .await
+        {
+            Ok(res) => {
+                for limit in res.into_inner().limits {
+                    println!(
+                        "{}/{} rate: {} burst: {}",
+                        Ipv4Addr::from_bits(limit.net),
+                        limit.prefix_len,
+                        limit.rate,
+                        limit.burst
+                    );
+                }
+            }
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    for (backends, canary) in [
+        (&opt.add_service_backends, false),
+        (&opt.add_service_canaries, true),
+    ] {
+        if backends.is_empty() {
+            continue;
+        }
+        match client
+            .add_service_backends(Request::new(parse_service_backends(backends, canary)))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    for (backends, canary) in [
+        (&opt.remove_service_backends, false),
+        (&opt.remove_service_canaries, true),
+    ] {
+        if backends.is_empty() {
+            continue;
+        }
+        match client
+            .remove_service_backends(Request::new(parse_service_backends(backends, canary)))
+            .await
+        {
+            Ok(res) => println!("Removed {} backends from service", res.into_inner().removed),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.set_service_canary_percent.is_empty() {
+        match client
+            .set_service_canary_percent(Request::new(parse_service_canary_percent(
+                &opt.set_service_canary_percent,
+            )))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if opt.list_services {
+        match client
+            .list_services(Request::new(ListServicesRequest {}))
+            .await
+        {
+            Ok(res) => {
+                for service in res.into_inner().services {
+                    println!(
+                        "{}: backends: {} canaries: {} canary_percent: {}",
+                        service.service_id,
+                        format_addresses(&service.backends),
+                        format_addresses(&service.canaries),
+                        service.canary_percent
+                    );
+                }
+            }
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if let Some(Command::Backend { action }) = &opt.command {
+        let (addr, state, drain_timeout_secs) = match *action {
+            BackendAction::Drain { addr, timeout } => {
+                (addr, BackendState::BackendDraining, timeout)
+            }
+            BackendAction::Enable { addr } => (addr, BackendState::BackendEnabled, 0),
+            BackendAction::Disable { addr } => (addr, BackendState::BackendDisabled, 0),
+            BackendAction::List => {
+                match client
+                    .list_backend_states(Request::new(ListBackendStatesRequest {}))
+                    .await
+                {
+                    Ok(res) => {
+                        for backend in res.into_inner().backends {
+                            let state = match backend.state() {
+                                BackendState::BackendEnabled => "enabled",
+                                BackendState::BackendDraining => "draining",
+                                BackendState::BackendDisabled => "disabled",
+                            };
+                            println!(
+                                "{}: {} disabled_in_secs: {}",
+                                Ipv4Addr::from_bits(backend.addr),
+                                state,
+                                backend.disabled_in_secs
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        panic!("Error contacting XDP hook: {:?}", e);
+                    }
+                }
+                return Ok(());
+            }
+        };
+        match client
+            .set_backend_state(Request::new(SetBackendStateRequest {
+                addr: addr.to_bits(),
+                state: state.into(),
+                drain_timeout_secs,
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if let Some(Command::TopTalkers { limit }) = opt.command {
+        match client
+            .get_top_talkers(Request::new(GetTopTalkersRequest { limit }))
+            .await
+        {
+            Ok(res) => {
+                let res = res.into_inner();
+                println!("Packets in the last {} seconds", res.window_secs);
+                println!("Sources:");
+                for talker in res.sources {
+                    println!("  {} packets: {}", Ipv4Addr::from_bits(talker.addr), talker.packets);
+                }
+                println!("Targets:");
+                for talker in res.targets {
+                    println!("  {} packets: {}", Ipv4Addr::from_bits(talker.addr), talker.packets);
+                }
+            }
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+        return Ok(());
+    }
+
+    if !opt.block_top_talkers.is_empty() {
+        let (packets_per_sec, block_secs) = parse_top_talker_blocking(&opt.block_top_talkers);
+        match client
+            .set_top_talker_blocking(Request::new(SetTopTalkerBlockingRequest {
+                packets_per_sec,
+                block_secs,
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if opt.backend_health {
+        match client
+            .get_backend_health(Request::new(GetBackendHealthRequest {}))
+            .await
+        {
+            Ok(res) => {
+                for backend in res.into_inner().backends {
+                    println!(
+                        "{}: {} probes_succeeded: {} probes_failed: {} last_rtt_micros: {}",
+                        Ipv4Addr::from_bits(backend.addr),
+                        if backend.up { "up" } else { "down" },
+                        backend.probes_succeeded,
+                        backend.probes_failed,
+                        backend.last_rtt_micros
+                    );
+                }
+            }
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.set_backend_rate_limit.is_empty() {
+        let limit = parse_prefix_rate_limit(&opt.set_backend_rate_limit);
+        match client
+            .set_backend_rate_limit(Request::new(SetPrefixRateLimitRequest { limit: Some(limit) }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.remove_backend_rate_limit.is_empty() {
+        let (net, prefix_len) = parse_prefix(&opt.remove_backend_rate_limit);
+        match client
+            .remove_backend_rate_limit(Request::new(RemovePrefixRateLimitRequest {
+                net,
+                prefix_len,
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if opt.list_backend_rate_limits {
+        match client
+            .list_backend_rate_limits(Request::new(ListPrefixRateLimitsRequest {}))
+            .await
+        {
+            Ok(res) => {
+                for limit in res.into_inner().limits {
+                    println!(
+                        "{}/{} rate: {} burst: {}",
+                        Ipv4Addr::from_bits(limit.net),
+                        limit.prefix_len,
+                        limit.rate,
+                        limit.burst
+                    );
+                }
+            }
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    // Rotate keys before requiring them, so that enabling authentication with a new key
+    // works in a single invocation
+    if !opt.rotate_header_key.is_empty() {
+        let key = match parse_key(&opt.rotate_header_key) {
+            Some(key) => key,
+            None => panic!("Invalid header key:  {}", opt.rotate_header_key),
+        };
+        match client
+            .rotate_header_key(Request::new(RotateKeyRequest { key: key.to_vec() }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if opt.retire_previous_header_key {
+        match client
+            .retire_previous_header_key(Request::new(RetirePreviousKeyRequest {}))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.set_header_auth_policy.is_empty() {
+        match client
+            .set_header_auth_policy(Request::new(SetHeaderAuthPolicyRequest {
+                policy: parse_header_auth_policy(&opt.set_header_auth_policy).into(),
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if let Some(window_secs) = opt.set_seen_client_window {
+        match client
+            .set_seen_client_window(Request::new(SetSeenClientWindowRequest { window_secs }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.set_challenge_policy.is_empty() {
+        match client
+            .set_challenge_policy(Request::new(SetChallengePolicyRequest {
+                policy: parse_challenge_policy(&opt.set_challenge_policy).into(),
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if let Some(ratio) = opt.set_max_amplification {
+        match client
+            .set_max_amplification(Request::new(SetMaxAmplificationRequest { ratio }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.rotate_client_token_key.is_empty() {
+        let key = match parse_key(&opt.rotate_client_token_key) {
+            Some(key) => key,
+            None => panic!("Invalid client token key:  {}", opt.rotate_client_token_key),
+        };
+        match client
+            .rotate_client_token_key(Request::new(RotateKeyRequest { key: key.to_vec() }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if opt.retire_previous_client_token_key {
+        match client
+            .retire_previous_client_token_key(Request::new(RetirePreviousKeyRequest {}))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.set_client_token_policy.is_empty() {
+        match client
+            .set_client_token_policy(Request::new(SetClientTokenPolicyRequest {
+                policy: parse_client_token_policy(&opt.set_client_token_policy).into(),
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if let Some(timeout_secs) = opt.set_flow_idle_timeout {
+        match client
+            .set_flow_idle_timeout(Request::new(SetFlowIdleTimeoutRequest { timeout_secs }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.flush_flows.is_empty() {
+        let backend = match opt.flush_flows.as_str() {
+            "all" => 0,
+            addr => addr.parse::<Ipv4Addr>().unwrap().to_bits(),
+        };
+        match client
+            .flush_flows(Request::new(FlushFlowsRequest { backend }))
+            .await
+        {
+            Ok(res) => println!("Flushed {} flows", res.into_inner().flushed),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.pin_flow.is_empty() {
+        match client
+            .pin_flow(Request::new(parse_pin_flow(&opt.pin_flow)))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.unpin_flow.is_empty() {
+        let client_addr = parse_client_address(&opt.unpin_flow);
+        match client
+            .unpin_flow(Request::new(UnpinFlowRequest {
+                client_ip: client_addr.ip().to_bits(),
+                client_port: client_addr.port() as u32,
+            }))
+            .await
+        {
+            Ok(res) => {
+                if !res.into_inner().unpinned {
+                    println!("Flow {} is not pinned", client_addr);
+                }
+            }
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if let Some(limit) = opt.list_flows {
+        match client
+            .list_flows(Request::new(ListFlowsRequest { backend: 0, limit }))
+            .await
+        {
+            Ok(res) => {
+                for flow in res.into_inner().flows {
+                    println!(
+                        "{}:{} service: {} backend: {} idle_millis: {}{}",
+                        Ipv4Addr::from_bits(flow.client_ip),
+                        flow.client_port,
+                        flow.service_id,
+                        Ipv4Addr::from_bits(flow.backend),
+                        flow.idle_millis,
+                        if flow.pinned { " pinned" } else { "" }
+                    );
+                }
+            }
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if let Some(prefix_len) = opt.set_traffic_client_prefix_len {
+        match client
+            .set_traffic_client_prefix_len(Request::new(SetTrafficClientPrefixLenRequest {
+                prefix_len,
+            }))
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    if !opt.list_traffic.is_empty() {
+        let keyed = match opt.list_traffic.as_str() {
+            "backends" => TrafficKeyed::TrafficByBackend,
+            "clients" => TrafficKeyed::TrafficByClientPrefix,
+            _ => panic!("Invalid traffic counters:  {}", opt.list_traffic),
+        };
+        let mut page_token = 0;
+        loop {
+            let req = ListTrafficCountersRequest {
+                keyed: keyed.into(),
+                page_token,
+                limit: TRAFFIC_PAGE_SIZE,
+            };
+            let res = match client.list_traffic_counters(Request::new(req)).await {
+                Ok(res) => res.into_inner(),
+                Err(e) => {
+                    panic!("Error contacting XDP hook: {:?}", e);
+                }
+            };
+            for counter in res.counters {
+                println!(
+                    "{}/{} requests: {} request_bytes: {} responses: {} response_bytes: {} \
+                     idle_millis: {}",
+                    Ipv4Addr::from_bits(counter.addr),
+                    counter.prefix_len,
+                    counter.requests,
+                    counter.request_bytes,
+                    counter.responses,
+                    counter.response_bytes,
+                    counter.idle_millis
+                );
+            }
+            if res.next_page_token == 0 {
+                break;
+            }
+            page_token = res.next_page_token;
+        }
+    }
+
+    if let Some(limit) = opt.top_rate_limited_clients {
+        match client
+            .get_top_rate_limited_clients(Request::new(GetTopRateLimitedClientsRequest { limit }))
+            .await
+        {
+            Ok(res) => {
+                for rate_limited in res.into_inner().clients {
+                    println!(
+                        "{} dropped: {}",
+                        Ipv4Addr::from_bits(rate_limited.addr),
+                        rate_limited.dropped_packets
+                    );
+                }
+            }
+            Err(e) => {
+                panic!("Error contacting XDP hook: {:?}", e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_ip_mask(s: &str) -> (u32, u32) {
+    if s.is_empty() {
+        return (0, 0xffffffff);
+    }
+
+    let parts: Vec<&str> = s.split('/').collect();
+    if parts.len() != 2 {
+        panic!("Invalid IP/mask:  {}", s);
+    }
+
+    let net = parts[0].parse::<Ipv4Addr>().unwrap().to_bits();
+    let mask = 0xffffffff_u32 << (32 - parts[1].parse::<u32>().unwrap());
+
+    (net, mask)
+}
+
+fn parse_mac_address(s: &str) -> u64 {
+    let parts: Vec<&str> = s.split(':').collect();
+    if parts.len() != 6 {
+        panic!("Invalid MAC address:  {}", s);
+    }
+
+    let mut res = 0;
+    for part in parts.iter() {
+        let byte = u8::from_str_radix(part, 16).unwrap();
+        res = (res << 8) | byte as u64;
+    }
+
+    res
+}
+
+fn parse_ip_options_policy(s: &str) -> IpOptionsPolicy {
+    match s {
+        "forward" => IpOptionsPolicy::IpOptionsForward,
+        "drop" => IpOptionsPolicy::IpOptionsDrop,
+        _ => panic!("Invalid IP options policy:  {}", s),
+    }
+}
+
+fn parse_udp_zero_checksum_policy(s: &str) -> UdpZeroChecksumPolicy {
+    match s {
+        "keep" => UdpZeroChecksumPolicy::UdpZeroChecksumKeep,
+        "calculate" => UdpZeroChecksumPolicy::UdpZeroChecksumCalculate,
+        _ => panic!("Invalid UDP zero checksum policy:  {}", s),
+    }
+}
+
+fn parse_ttl_expired_policy(s: &str) -> TtlExpiredPolicy {
+    match s {
+        "drop" => TtlExpiredPolicy::TtlExpiredDrop,
+        "icmp" => TtlExpiredPolicy::TtlExpiredIcmp,
+        _ => panic!("Invalid TTL expired policy:  {}", s),
+    }
+}
+
+fn parse_fragment_policy(s: &str) -> FragmentPolicy {
+    match s {
+        "drop" => FragmentPolicy::FragmentsDrop,
+        "pass" => FragmentPolicy::FragmentsPass,
+        "forward-first" => FragmentPolicy::FragmentsForwardFirst,
+        _ => panic!("Invalid fragment policy:  {}", s),
+    }
+}
+
+fn parse_header_auth_policy(s: &str) -> HeaderAuthPolicy {
+    match s {
+        "disabled" => HeaderAuthPolicy::HeaderAuthDisabled,
+        "required" => HeaderAuthPolicy::HeaderAuthRequired,
+        _ => panic!("Invalid header authentication policy:  {}", s),
+    }
+}
+
+fn parse_client_token_policy(s: &str) -> ClientTokenPolicy {
+    match s {
+        "disabled" => ClientTokenPolicy::ClientTokensDisabled,
+        "enabled" => ClientTokenPolicy::ClientTokensEnabled,
+        _ => panic!("Invalid client token policy:  {}", s),
+    }
+}
+
+fn parse_challenge_policy(s: &str) -> ChallengePolicy {
+    match s {
+        "disabled" => ChallengePolicy::ChallengesDisabled,
+        "required" => ChallengePolicy::ChallengesRequired,
+        _ => panic!("Invalid challenge policy:  {}", s),
+    }
+}
+
+// Parses a rate in packets per second and a block duration in seconds (e.g. 50000,300),
+// or "0" to stop blocking
+fn parse_top_talker_blocking(s: &str) -> (u64, u32) {
+    if s == "0" {
+        return (0, 0);
+    }
+    match s.split_once(',') {
+        Some((rate, secs)) => (rate.parse().unwrap(), secs.parse().unwrap()),
+        None => panic!("Invalid top talker blocking:  {}", s),
+    }
+}
+
+// Parses an old and a new backend address with optional expiry in seconds (e.g.
+// 10.0.3.10=10.0.3.20,3600)
+fn parse_backend_remap(s: &str) -> BackendRemap {
+    let (old_backend, new_backend) = match s.split_once('=') {
+        Some(parts) => parts,
+        None => panic!("Invalid backend remap:  {}", s),
+    };
+    let (new_backend, expires_in_secs) = match new_backend.split_once(',') {
+        Some((addr, secs)) => (addr, secs.parse().unwrap()),
+        None => (new_backend, 0),
+    };
+
+    BackendRemap {
+        old_backend: old_backend.parse::<Ipv4Addr>().unwrap().to_bits(),
+        new_backend: new_backend.parse::<Ipv4Addr>().unwrap().to_bits(),
+        expires_in_secs,
+        hits: 0,
+    }
+}
+
+// Reads blocklist entries from a file with one prefix and optional expiry in seconds
+// per line. Empty lines and lines starting with '#' are skipped.
+fn read_blocklist_file(path: &str) -> Vec<BlocklistEntry> {
+    let contents = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Failed to read blocklist file {}: {}", path, e));
+
+    let mut entries = Vec::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        let (net, prefix_len) = parse_prefix(parts.next().unwrap());
+        let expires_in_secs = match parts.next() {
+            Some(secs) => secs.parse::<u64>().unwrap(),
+            None => 0,
+        };
+        entries.push(BlocklistEntry {
+            net,
+            prefix_len,
+            expires_in_secs,
+            hits: 0,
+        });
+    }
+
+    entries
+}
+
+// Parses a prefix (e.g. 192.0.2.0/24). A plain address is a /32 prefix.
+fn parse_prefix(s: &str) -> (u32, u32) {
+    let (addr, prefix_len) = match s.split_once('/') {
+        Some((addr, len)) => (addr, len.parse::<u32>().unwrap()),
+        None => (s, 32),
+    };
+    if prefix_len > 32 {
+        panic!("Invalid prefix:  {}", s);
+    }
+
+    (addr.parse::<Ipv4Addr>().unwrap().to_bits(), prefix_len)
+}
+
+fn parse_client_address(s: &str) -> SocketAddrV4 {
+    match s.parse() {
+        Ok(addr) => addr,
+        Err(_) => panic!("Invalid client address:  {}", s),
+    }
+}
+
+// Parses a client address and port, service ID and backend (e.g. 192.0.2.1:4000,7,10.0.3.10)
+fn parse_pin_flow(s: &str) -> PinFlowRequest {
+    let parts: Vec<&str> = s.split(',').collect();
+    let [client_addr, service_id, backend] = parts[..] else {
+        panic!("Invalid flow:  {}", s);
+    };
+    let client_addr = parse_client_address(client_addr);
+
+    PinFlowRequest {
+        client_ip: client_addr.ip().to_bits(),
+        client_port: client_addr.port() as u32,
+        service_id: service_id.parse().unwrap(),
+        backend: backend.parse::<Ipv4Addr>().unwrap().to_bits(),
+    }
+}
+
+// Parses packets per second and optional burst (e.g. 1000,2000)
+fn parse_rate_limit(s: &str) -> (u64, u64) {
+    match s.split_once(',') {
+        Some((rate, burst)) => (rate.parse().unwrap(), burst.parse().unwrap()),
+        None => (s.parse().unwrap(), 0),
+    }
+}
+
+// Parses a service ID and its backends (e.g. 7=10.0.3.10,10.0.3.11)
+fn parse_service_backends(s: &str, canary: bool) -> ServiceBackendsRequest {
+    let (service_id, backends) = match s.split_once('=') {
+        Some((id, backends)) => (id.parse().unwrap(), backends),
+        None => panic!("Invalid service backends:  {}", s),
+    };
+    let backends = backends
+        .split(',')
+        .map(|addr| addr.parse::<Ipv4Addr>().unwrap().to_bits())
+        .collect();
+
+    ServiceBackendsRequest {
+        service_id,
+        backends,
+        canary,
+    }
+}
+
+// Parses a service ID and the percentage of its clients sent to canaries (e.g. 7=10)
+fn parse_service_canary_percent(s: &str) -> SetServiceCanaryPercentRequest {
+    match s.split_once('=') {
+        Some((id, percent)) => SetServiceCanaryPercentRequest {
+            service_id: id.parse().unwrap(),
+            canary_percent: percent.parse().unwrap(),
+        },
+        None => panic!("Invalid canary percentage:  {}", s),
+    }
+}
+
+fn format_addresses(addrs: &[u32]) -> String {
+    let addrs: Vec<String> = addrs
+        .iter()
+        .map(|&addr| Ipv4Addr::from_bits(addr).to_string())
+        .collect();
+    addrs.join(",")
+}
+
+fn parse_prefix_rate_limit(s: &str) -> PrefixRateLimit {
+    let (prefix, limit) = match s.split_once(',') {
+        Some(parts) => parts,
+        None => panic!("Invalid prefix rate limit:  {}", s),
+    };
+    let (net, prefix_len) = parse_prefix(prefix);
+    let (rate, burst) = parse_rate_limit(limit);
+    PrefixRateLimit {
+        net,
+        prefix_len,
+        rate,
+        burst,
+    }
 }
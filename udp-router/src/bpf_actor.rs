@@ -1,11 +1,52 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+    ffi::CString,
+    fs,
+    io::{self, Read},
+    net::Ipv4Addr,
+    time::{Duration, Instant},
+};
+
 use aya::{
-    maps::{Array, MapData, PerCpuArray, PerCpuValues},
+    maps::{
+        lpm_trie::{Key, LpmTrie},
+        Array, DevMap, HashMap, MapData, MapError, PerCpuArray, PerCpuHashMap, PerCpuValues,
+    },
     programs::{Xdp, XdpFlags},
     Ebpf,
 };
 use aya_log::EbpfLogger;
 use log::warn;
-use tokio::sync::{mpsc, oneshot};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time,
+};
+use udp_router_common::{
+    flow_key, hyperloglog, maglev,
+    siphash::{key_words, KEY_LEN},
+    sketch, BackendRemap, BlocklistEntry, ClientRegisters, FlowAffinity, HeavyHitterSketch,
+    RateLimit, SipKey, TokenBucket, TrafficCounter, BACKEND_DISABLED, BACKEND_DOWN,
+    BACKEND_DRAINING, BACKEND_INTERFACE, BACKEND_UP, CLIENT_INTERFACE, CONFIG_CHALLENGE_POLICY,
+    CONFIG_CLIENT_BURST, CONFIG_CLIENT_RATE_LIMIT, CONFIG_CLIENT_TOKEN_POLICY,
+    CONFIG_FLOW_IDLE_TIMEOUT, CONFIG_FRAGMENT_POLICY, CONFIG_HEADER_AUTH_POLICY,
+    CONFIG_IP_OPTIONS_POLICY, CONFIG_MAX_AMPLIFICATION, CONFIG_MIN_ROUTING_HEADER_LEN,
+    CONFIG_SEEN_CLIENT_WINDOW, CONFIG_TRAFFIC_CLIENT_PREFIX_LEN, CONFIG_TTL_EXPIRED_POLICY,
+    CONFIG_UDP_ZERO_CHECKSUM_POLICY, COOKIE_LIFETIME_SECS, COUNTER_AMPLIFICATION_DROPPED,
+    COUNTER_BACKEND_OVERLOAD_DROPPED, COUNTER_BACKEND_REMAPPED, COUNTER_BLOCKLIST_DROPPED,
+    COUNTER_CHALLENGES_SENT, COUNTER_CLIENT_TOKEN_DROPPED, COUNTER_COOKIES_VERIFIED,
+    COUNTER_COOKIE_DROPPED, COUNTER_FIRST_FRAGMENTS_FORWARDED, COUNTER_FRAGMENTS_DROPPED,
+    COUNTER_FRAGMENTS_PASSED, COUNTER_HEADER_AUTH_DROPPED, COUNTER_ICMP_TIME_EXCEEDED_SENT,
+    COUNTER_IP_LENGTH_DROPPED, COUNTER_IP_OPTIONS_DROPPED, COUNTER_NON_UNICAST_DROPPED,
+    COUNTER_RATE_LIMITED_DROPPED, COUNTER_SERVICE_UNAVAILABLE_DROPPED,
+    COUNTER_SHORT_ROUTING_HEADER_DROPPED, COUNTER_TARGET_BROADCAST_DROPPED,
+    COUNTER_TARGET_LINK_LOCAL_DROPPED, COUNTER_TARGET_LOCAL_NETWORK_DROPPED,
+    COUNTER_TARGET_LOOPBACK_DROPPED, COUNTER_TARGET_MULTICAST_DROPPED,
+    COUNTER_TARGET_ROUTER_DROPPED, COUNTER_TARGET_UNSPECIFIED_DROPPED, COUNTER_TTL_EXPIRED_DROPPED,
+    COUNTER_UDP_CHECKSUM_ADDED, COUNTER_UDP_LENGTH_DROPPED, COUNTER_UNAVAILABLE_BACKEND_SKIPPED,
+    COUNTER_UNSEEN_CLIENT_DROPPED, KEY_CURRENT, KEY_PREVIOUS, MAGLEV_TABLE_SIZE,
+    MAX_SERVICE_BACKENDS, SKETCH_CANDIDATES, SKETCH_SOURCES, SKETCH_TARGETS,
+};
 
 use crate::Opt;
 
@@ -43,8 +84,369 @@ impl BpfActorHandle {
         let _ = self.sender.send(msg).await;
     }
 
-    pub async fn set_gateway_mac_address(&self, mac: u64) {
-        let msg = BpfActorMessage::SetGatewayMacAddress { mac };
+    pub async fn set_gateway_mac_address(&self, side: u32, mac: u64) {
+        let msg = BpfActorMessage::SetGatewayMacAddress { side, mac };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn set_source_mac_address(&self, side: u32, mac: u64) {
+        let msg = BpfActorMessage::SetSourceMacAddress { side, mac };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn set_ip_options_policy(&self, policy: u64) {
+        let msg = BpfActorMessage::SetIpOptionsPolicy { policy };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn set_udp_zero_checksum_policy(&self, policy: u64) {
+        let msg = BpfActorMessage::SetUdpZeroChecksumPolicy { policy };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn set_min_routing_header_len(&self, len: u32) {
+        let msg = BpfActorMessage::SetMinRoutingHeaderLen { len };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn set_ttl_expired_policy(&self, policy: u64) {
+        let msg = BpfActorMessage::SetTtlExpiredPolicy { policy };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn set_fragment_policy(&self, policy: u64) {
+        let msg = BpfActorMessage::SetFragmentPolicy { policy };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn add_blocklist_rules(&self, rules: Vec<BlocklistRule>) -> Result<(), MapError> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::AddBlocklistRules {
+            rules,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn remove_blocklist_rules(&self, rules: Vec<BlocklistRule>) -> usize {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::RemoveBlocklistRules {
+            rules,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn list_blocklist_rules(&self) -> Vec<BlocklistRule> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::ListBlocklistRules { respond_to: send };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn add_backend_remaps(&self, remaps: Vec<Remap>) -> Result<(), MapError> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::AddBackendRemaps {
+            remaps,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn remove_backend_remaps(&self, old_backends: Vec<u32>) -> usize {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::RemoveBackendRemaps {
+            old_backends,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn list_backend_remaps(&self) -> Vec<Remap> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::ListBackendRemaps { respond_to: send };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn set_client_rate_limit(&self, rate: u64, burst: u64) {
+        let msg = BpfActorMessage::SetClientRateLimit { rate, burst };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn set_prefix_rate_limit(
+        &self,
+        limited: RateLimited,
+        limit: PrefixRateLimit,
+    ) -> Result<(), MapError> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::SetPrefixRateLimit {
+            limited,
+            limit,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn remove_prefix_rate_limit(
+        &self,
+        limited: RateLimited,
+        net: u32,
+        prefix_len: u32,
+    ) -> bool {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::RemovePrefixRateLimit {
+            limited,
+            net,
+            prefix_len,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn list_prefix_rate_limits(&self, limited: RateLimited) -> Vec<PrefixRateLimit> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::ListPrefixRateLimits {
+            limited,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn add_service_backends(
+        &self,
+        service_id: u32,
+        pool: Pool,
+        backends: Vec<u32>,
+    ) -> bool {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::AddServiceBackends {
+            service_id,
+            pool,
+            backends,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn remove_service_backends(
+        &self,
+        service_id: u32,
+        pool: Pool,
+        backends: Vec<u32>,
+    ) -> usize {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::RemoveServiceBackends {
+            service_id,
+            pool,
+            backends,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn set_service_canary_percent(&self, service_id: u32, percent: u32) -> bool {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::SetServiceCanaryPercent {
+            service_id,
+            percent,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn list_services(&self) -> Vec<Service> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::ListServices { respond_to: send };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn report_probes(&self, results: Vec<ProbeResult>) {
+        let msg = BpfActorMessage::ReportProbes { results };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn get_backend_health(&self) -> Vec<BackendHealth> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::GetBackendHealth { respond_to: send };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn set_backend_maintenance(
+        &self,
+        addr: u32,
+        maintenance: Option<Maintenance>,
+    ) -> Result<(), MapError> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::SetBackendMaintenance {
+            addr,
+            maintenance,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn list_backend_maintenance(&self) -> Vec<(u32, Maintenance)> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::ListBackendMaintenance { respond_to: send };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn get_top_rate_limited_clients(&self, limit: usize) -> Vec<(u32, u64)> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::GetTopRateLimitedClients {
+            limit,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn set_header_auth_policy(&self, policy: u64) {
+        let msg = BpfActorMessage::SetHeaderAuthPolicy { policy };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn set_client_token_policy(&self, policy: u64) {
+        let msg = BpfActorMessage::SetClientTokenPolicy { policy };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn set_seen_client_window(&self, window: Duration) {
+        let msg = BpfActorMessage::SetSeenClientWindow { window };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn set_flow_idle_timeout(&self, timeout: Duration) {
+        let msg = BpfActorMessage::SetFlowIdleTimeout { timeout };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn list_flows(&self, backend: Option<u32>, limit: usize) -> Vec<Flow> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::ListFlows {
+            backend,
+            limit,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn flush_flows(&self, backend: Option<u32>) -> usize {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::FlushFlows {
+            backend,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn pin_flow(&self, flow: Flow) -> Result<(), MapError> {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::PinFlow {
+            flow,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn unpin_flow(&self, client_ip: u32, client_port: u16) -> bool {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::UnpinFlow {
+            client_ip,
+            client_port,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn set_traffic_client_prefix_len(&self, prefix_len: u32) {
+        let msg = BpfActorMessage::SetTrafficClientPrefixLen { prefix_len };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn list_traffic(&self, keyed: TrafficKeyed, start: u32, limit: usize) -> TrafficPage {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::ListTraffic {
+            keyed,
+            start,
+            limit,
+            respond_to: send,
+        };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn get_top_talkers(&self) -> TopTalkers {
+        let (send, recv) = oneshot::channel();
+        let msg = BpfActorMessage::GetTopTalkers { respond_to: send };
+
+        let _ = self.sender.send(msg).await;
+        recv.await.expect("BPF actor has been killed")
+    }
+
+    pub async fn set_top_talker_blocking(&self, blocking: Option<TopTalkerBlocking>) {
+        let msg = BpfActorMessage::SetTopTalkerBlocking { blocking };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn set_max_amplification(&self, ratio: u64) {
+        let msg = BpfActorMessage::SetMaxAmplification { ratio };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn set_challenge_policy(&self, policy: u64) {
+        let msg = BpfActorMessage::SetChallengePolicy { policy };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn rotate_key(&self, keyed: Keyed, key: [u8; KEY_LEN]) {
+        let msg = BpfActorMessage::RotateKey { keyed, key };
+        let _ = self.sender.send(msg).await;
+    }
+
+    pub async fn retire_previous_key(&self, keyed: Keyed) {
+        let msg = BpfActorMessage::RetirePreviousKey { keyed };
         let _ = self.sender.send(msg).await;
     }
 }
@@ -55,7 +457,10 @@ impl BpfActorHandle {
 
 macro_rules! read_metric {
     ($var:expr) => {{
-        let values: PerCpuValues<u64> = $var.get(&0, 0).expect("unable to read metric");
+        read_metric!($var, COUNTER_INDEX_SINGLE)
+    }};
+    ($var:expr, $index:expr) => {{
+        let values: PerCpuValues<u64> = $var.get(&$index, 0).expect("unable to read metric");
         let mut tmp = 0;
         for cpu_val in values.iter() {
             tmp += *cpu_val;
@@ -70,15 +475,59 @@ macro_rules! write_map {
     }};
 }
 
+// Index of the value in counter maps holding a single counter
+const COUNTER_INDEX_SINGLE: u32 = 0;
+
+// Interval between removals of expired map entries
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(10);
+
+// Number of probes in a row a backend must fail to be marked down, and answer to be
+// marked up again
+const UNHEALTHY_THRESHOLD: u32 = 3;
+const HEALTHY_THRESHOLD: u32 = 2;
+
+// Time without packets after which the traffic counters of a backend or client prefix
+// are removed
+const TRAFFIC_IDLE_TIMEOUT: Duration = Duration::from_secs(600);
+
+// Length of the windows heavy hitters are counted in
+const HEAVY_HITTER_WINDOW: Duration = Duration::from_secs(10);
+
+// Length of the windows distinct clients are counted in
+const UNIQUE_CLIENT_WINDOW: Duration = Duration::from_secs(60);
+
+// Interval between replacements of the cookie key by a random key
+const COOKIE_KEY_INTERVAL: Duration = Duration::from_secs(COOKIE_LIFETIME_SECS);
+
 struct StatsMaps {
     total_packets: PerCpuArray<MapData, u64>,
     client_to_server_packets: PerCpuArray<MapData, u64>,
     server_to_client_packets: PerCpuArray<MapData, u64>,
+    counters: PerCpuArray<MapData, u64>,
+    client_buckets: HashMap<MapData, u32, TokenBucket>,
+    backend_buckets: HashMap<MapData, u32, TokenBucket>,
+    service_backend_packets: PerCpuHashMap<MapData, u32, u64>,
+    backend_traffic: PerCpuHashMap<MapData, u32, TrafficCounter>,
+    client_prefix_traffic: PerCpuHashMap<MapData, u32, TrafficCounter>,
+    heavy_hitters: PerCpuArray<MapData, HeavyHitterSketch>,
+    client_registers: PerCpuArray<MapData, ClientRegisters>,
 }
 struct ConfigMaps {
     local_net_and_mask: Array<MapData, u64>,
     backend_net_and_mask: Array<MapData, u64>,
     gateway_mac_address: Array<MapData, u64>,
+    source_mac_address: Array<MapData, u64>,
+    config: Array<MapData, u64>,
+    blocklist: LpmTrie<MapData, u32, BlocklistEntry>,
+    client_rate_limits: LpmTrie<MapData, u32, RateLimit>,
+    backend_rate_limits: LpmTrie<MapData, u32, RateLimit>,
+    header_keys: Array<MapData, SipKey>,
+    client_token_keys: Array<MapData, SipKey>,
+    cookie_keys: Array<MapData, SipKey>,
+    service_tables: Array<MapData, u32>,
+    backend_states: HashMap<MapData, u32, u32>,
+    service_flows: HashMap<MapData, u64, FlowAffinity>,
+    backend_remaps: HashMap<MapData, u32, BackendRemap>,
 }
 
 #[derive(Clone, Debug)]
@@ -86,61 +535,653 @@ pub struct RouterStatistics {
     pub total_packets: u64,
     pub client_to_server_packets: u64,
     pub server_to_client_packets: u64,
+    pub non_unicast_dropped_packets: u64,
+    pub ip_options_dropped_packets: u64,
+    pub udp_checksum_added_packets: u64,
+    pub ip_length_dropped_packets: u64,
+    pub udp_length_dropped_packets: u64,
+    pub short_routing_header_dropped_packets: u64,
+    pub ttl_expired_dropped_packets: u64,
+    pub icmp_time_exceeded_sent_packets: u64,
+    pub fragments_dropped_packets: u64,
+    pub fragments_passed_packets: u64,
+    pub first_fragments_forwarded_packets: u64,
+    pub target_unspecified_dropped_packets: u64,
+    pub target_loopback_dropped_packets: u64,
+    pub target_multicast_dropped_packets: u64,
+    pub target_broadcast_dropped_packets: u64,
+    pub target_link_local_dropped_packets: u64,
+    pub target_local_network_dropped_packets: u64,
+    pub target_router_dropped_packets: u64,
+    pub rate_limited_dropped_packets: u64,
+    pub blocklist_dropped_packets: u64,
+    pub backend_overload_dropped_packets: u64,
+    /// Backends with a token bucket or packets through a service
+    pub backends: Vec<BackendStatistics>,
+    pub header_auth_dropped_packets: u64,
+    pub client_token_dropped_packets: u64,
+    pub unseen_client_dropped_packets: u64,
+    pub amplification_dropped_packets: u64,
+    pub challenges_sent_packets: u64,
+    pub cookies_verified_packets: u64,
+    pub cookie_dropped_packets: u64,
+    pub service_unavailable_dropped_packets: u64,
+    pub unavailable_backend_skipped_packets: u64,
+    pub backend_remapped_packets: u64,
+    /// Distinct clients in the last complete window, estimated
+    pub unique_clients: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BackendStatistics {
+    pub addr: u32,
+    /// Packets dropped as the backend exceeded its rate limit
+    pub shed_packets: u64,
+    /// Packets forwarded to the backend through a service
+    pub service_packets: u64,
 }
 
 //
-// BPF Actor
+// Blocklist
 //
 
-pub enum BpfActorMessage {
-    GetStats {
-        respond_to: oneshot::Sender<RouterStatistics>,
-    },
-    SetLocalNetAndMask {
-        net: u32,
-        mask: u32,
-    },
-    SetBackendNetAndMask {
-        net: u32,
-        mask: u32,
-    },
-    SetGatewayMacAddress {
-        mac: u64,
-    },
+#[derive(Clone, Debug)]
+pub struct BlocklistRule {
+    pub net: u32,
+    pub prefix_len: u32,
+    /// Time until the rule expires, or None if it never does
+    pub expires_in: Option<Duration>,
+    pub hits: u64,
 }
 
-struct BpfActor {
-    receiver: mpsc::Receiver<BpfActorMessage>,
-    stats: StatsMaps,
-    configs: ConfigMaps,
+//
+// Backend remapping
+//
+
+#[derive(Clone, Debug)]
+pub struct Remap {
+    pub old_backend: u32,
+    pub new_backend: u32,
+    /// Time until the remap expires, or None if it never does
+    pub expires_in: Option<Duration>,
+    pub hits: u64,
 }
 
-impl BpfActor {
-    fn new(
-        receiver: mpsc::Receiver<BpfActorMessage>,
-        stats: StatsMaps,
-        configs: ConfigMaps,
-    ) -> Self {
-        Self {
-            receiver,
-            stats,
-            configs,
-        }
-    }
+//
+// Rate limiting
+//
 
-    fn handle_message(&mut self, msg: BpfActorMessage) {
-        match msg {
-            BpfActorMessage::GetStats { respond_to } => {
-                let _ = respond_to.send(self.get_stats());
-            }
-            BpfActorMessage::SetLocalNetAndMask { net, mask } => {
-                self.set_local_net_mask(net, mask);
-            }
-            BpfActorMessage::SetBackendNetAndMask { net, mask } => {
+// Traffic a prefix rate limit applies to
+#[derive(Clone, Copy, Debug)]
+pub enum RateLimited {
+    Clients,
+    Backends,
+}
+
+#[derive(Clone, Debug)]
+pub struct PrefixRateLimit {
+    pub net: u32,
+    pub prefix_len: u32,
+    pub rate: u64,
+    pub burst: u64,
+}
+
+//
+// Services
+//
+
+// Pool of the backends of a service
+#[derive(Clone, Copy, Debug)]
+pub enum Pool {
+    Backends,
+    Canaries,
+}
+
+#[derive(Clone, Debug)]
+pub struct Service {
+    pub id: u32,
+    pub backends: Vec<u32>,
+    pub canaries: Vec<u32>,
+    /// Percentage of clients sent to canaries
+    pub canary_percent: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ServicePools {
+    backends: BTreeSet<u32>,
+    canaries: BTreeSet<u32>,
+    canary_percent: u32,
+}
+
+impl ServicePools {
+    fn pool_mut(&mut self, pool: Pool) -> &mut BTreeSet<u32> {
+        match pool {
+            Pool::Backends => &mut self.backends,
+            Pool::Canaries => &mut self.canaries,
+        }
+    }
+}
+
+//
+// Session affinity
+//
+
+#[derive(Clone, Debug)]
+pub struct Flow {
+    pub client_ip: u32,
+    pub client_port: u16,
+    pub service_id: u32,
+    pub backend: u32,
+    /// Time since the last packet of the flow
+    pub idle: Duration,
+    pub pinned: bool,
+}
+
+//
+// Traffic accounting
+//
+
+// Map traffic is counted in
+#[derive(Clone, Copy, Debug)]
+pub enum TrafficKeyed {
+    Backends,
+    ClientPrefixes,
+}
+
+#[derive(Clone, Debug)]
+pub struct Traffic {
+    pub addr: u32,
+    pub prefix_len: u32,
+    pub requests: u64,
+    pub request_bytes: u64,
+    pub responses: u64,
+    pub response_bytes: u64,
+    /// Time since the last packet
+    pub idle: Duration,
+}
+
+#[derive(Clone, Debug)]
+pub struct TrafficPage {
+    /// Counters in order of address
+    pub traffic: Vec<Traffic>,
+    /// Address to list the next page from, or None if this is the last page
+    pub next: Option<u32>,
+}
+
+//
+// Heavy hitters
+//
+
+#[derive(Clone, Debug)]
+pub struct TopTalker {
+    pub addr: u32,
+    /// Estimated packets in the window
+    pub packets: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TopTalkers {
+    /// Clients sending the most packets, in descending order of packets
+    pub sources: Vec<TopTalker>,
+    /// Backends receiving the most packets from clients, in descending order of packets
+    pub targets: Vec<TopTalker>,
+    /// Length of the window the packets were counted in
+    pub window: Duration,
+}
+
+// Clients sending more packets per second than the rate in a window are added to the
+// blocklist for the duration
+#[derive(Clone, Copy, Debug)]
+pub struct TopTalkerBlocking {
+    pub rate: u64,
+    pub duration: Duration,
+}
+
+//
+// Health checks
+//
+
+#[derive(Clone, Debug)]
+pub struct ProbeResult {
+    pub addr: u32,
+    /// Round-trip time of the probe, or None if it was not answered in time
+    pub rtt: Option<Duration>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BackendHealth {
+    pub addr: u32,
+    pub up: bool,
+    pub probes_succeeded: u64,
+    pub probes_failed: u64,
+    /// Round-trip time of the last answered probe
+    pub last_rtt: Option<Duration>,
+    // Number of probes in a row whose result disagrees with the state
+    streak: u32,
+}
+
+// State of a backend taken out of services by the operator
+#[derive(Clone, Copy, Debug)]
+pub enum Maintenance {
+    /// Only receiving packets of the clients it serves, until disabled at the deadline
+    Draining {
+        until: Instant,
+    },
+    Disabled,
+}
+
+//
+// Keys
+//
+
+// Values made with a rotated SipHash key
+#[derive(Clone, Copy, Debug)]
+pub enum Keyed {
+    HeaderTags,
+    ClientTokens,
+    Cookies,
+}
+
+//
+// BPF Actor
+//
+
+pub enum BpfActorMessage {
+    GetStats {
+        respond_to: oneshot::Sender<RouterStatistics>,
+    },
+    SetLocalNetAndMask {
+        net: u32,
+        mask: u32,
+    },
+    SetBackendNetAndMask {
+        net: u32,
+        mask: u32,
+    },
+    SetGatewayMacAddress {
+        side: u32,
+        mac: u64,
+    },
+    SetSourceMacAddress {
+        side: u32,
+        mac: u64,
+    },
+    SetIpOptionsPolicy {
+        policy: u64,
+    },
+    SetUdpZeroChecksumPolicy {
+        policy: u64,
+    },
+    SetMinRoutingHeaderLen {
+        len: u32,
+    },
+    SetTtlExpiredPolicy {
+        policy: u64,
+    },
+    SetFragmentPolicy {
+        policy: u64,
+    },
+    AddBlocklistRules {
+        rules: Vec<BlocklistRule>,
+        respond_to: oneshot::Sender<Result<(), MapError>>,
+    },
+    RemoveBlocklistRules {
+        rules: Vec<BlocklistRule>,
+        respond_to: oneshot::Sender<usize>,
+    },
+    ListBlocklistRules {
+        respond_to: oneshot::Sender<Vec<BlocklistRule>>,
+    },
+    AddBackendRemaps {
+        remaps: Vec<Remap>,
+        respond_to: oneshot::Sender<Result<(), MapError>>,
+    },
+    RemoveBackendRemaps {
+        old_backends: Vec<u32>,
+        respond_to: oneshot::Sender<usize>,
+    },
+    ListBackendRemaps {
+        respond_to: oneshot::Sender<Vec<Remap>>,
+    },
+    SetClientRateLimit {
+        rate: u64,
+        burst: u64,
+    },
+    SetPrefixRateLimit {
+        limited: RateLimited,
+        limit: PrefixRateLimit,
+        respond_to: oneshot::Sender<Result<(), MapError>>,
+    },
+    RemovePrefixRateLimit {
+        limited: RateLimited,
+        net: u32,
+        prefix_len: u32,
+        respond_to: oneshot::Sender<bool>,
+    },
+    ListPrefixRateLimits {
+        limited: RateLimited,
+        respond_to: oneshot::Sender<Vec<PrefixRateLimit>>,
+    },
+    AddServiceBackends {
+        service_id: u32,
+        pool: Pool,
+        backends: Vec<u32>,
+        respond_to: oneshot::Sender<bool>,
+    },
+    RemoveServiceBackends {
+        service_id: u32,
+        pool: Pool,
+        backends: Vec<u32>,
+        respond_to: oneshot::Sender<usize>,
+    },
+    SetServiceCanaryPercent {
+        service_id: u32,
+        percent: u32,
+        respond_to: oneshot::Sender<bool>,
+    },
+    ListServices {
+        respond_to: oneshot::Sender<Vec<Service>>,
+    },
+    ReportProbes {
+        results: Vec<ProbeResult>,
+    },
+    GetBackendHealth {
+        respond_to: oneshot::Sender<Vec<BackendHealth>>,
+    },
+    SetBackendMaintenance {
+        addr: u32,
+        maintenance: Option<Maintenance>,
+        respond_to: oneshot::Sender<Result<(), MapError>>,
+    },
+    ListBackendMaintenance {
+        respond_to: oneshot::Sender<Vec<(u32, Maintenance)>>,
+    },
+    GetTopRateLimitedClients {
+        limit: usize,
+        respond_to: oneshot::Sender<Vec<(u32, u64)>>,
+    },
+    SetHeaderAuthPolicy {
+        policy: u64,
+    },
+    SetClientTokenPolicy {
+        policy: u64,
+    },
+    SetSeenClientWindow {
+        window: Duration,
+    },
+    SetFlowIdleTimeout {
+        timeout: Duration,
+    },
+    ListFlows {
+        backend: Option<u32>,
+        limit: usize,
+        respond_to: oneshot::Sender<Vec<Flow>>,
+    },
+    FlushFlows {
+        backend: Option<u32>,
+        respond_to: oneshot::Sender<usize>,
+    },
+    PinFlow {
+        flow: Flow,
+        respond_to: oneshot::Sender<Result<(), MapError>>,
+    },
+    UnpinFlow {
+        client_ip: u32,
+        client_port: u16,
+        respond_to: oneshot::Sender<bool>,
+    },
+    SetTrafficClientPrefixLen {
+        prefix_len: u32,
+    },
+    ListTraffic {
+        keyed: TrafficKeyed,
+        start: u32,
+        limit: usize,
+        respond_to: oneshot::Sender<TrafficPage>,
+    },
+    GetTopTalkers {
+        respond_to: oneshot::Sender<TopTalkers>,
+    },
+    SetTopTalkerBlocking {
+        blocking: Option<TopTalkerBlocking>,
+    },
+    SetMaxAmplification {
+        ratio: u64,
+    },
+    SetChallengePolicy {
+        policy: u64,
+    },
+    RotateKey {
+        keyed: Keyed,
+        key: [u8; KEY_LEN],
+    },
+    RetirePreviousKey {
+        keyed: Keyed,
+    },
+}
+
+struct BpfActor {
+    receiver: mpsc::Receiver<BpfActorMessage>,
+    stats: StatsMaps,
+    configs: ConfigMaps,
+    // Backend pools of services, from which their lookup tables are built
+    services: BTreeMap<u32, ServicePools>,
+    // Health of the backends of services, as last reported by the health checker
+    health: BTreeMap<u32, BackendHealth>,
+    // Backends taken out of services by the operator
+    maintenance: BTreeMap<u32, Maintenance>,
+    // Heavy hitters of the last complete window
+    top_talkers: TopTalkers,
+    top_talker_blocking: Option<TopTalkerBlocking>,
+    // Distinct clients of the last complete window
+    unique_clients: u64,
+}
+
+impl BpfActor {
+    fn new(
+        receiver: mpsc::Receiver<BpfActorMessage>,
+        stats: StatsMaps,
+        configs: ConfigMaps,
+    ) -> Self {
+        Self {
+            receiver,
+            stats,
+            configs,
+            services: BTreeMap::new(),
+            health: BTreeMap::new(),
+            maintenance: BTreeMap::new(),
+            top_talkers: TopTalkers::default(),
+            top_talker_blocking: None,
+            unique_clients: 0,
+        }
+    }
+
+    fn handle_message(&mut self, msg: BpfActorMessage) {
+        match msg {
+            BpfActorMessage::GetStats { respond_to } => {
+                let _ = respond_to.send(self.get_stats());
+            }
+            BpfActorMessage::SetLocalNetAndMask { net, mask } => {
+                self.set_local_net_mask(net, mask);
+            }
+            BpfActorMessage::SetBackendNetAndMask { net, mask } => {
                 self.set_backend_net_mask(net, mask);
             }
-            BpfActorMessage::SetGatewayMacAddress { mac } => {
-                self.set_gateway_mac_address(mac);
+            BpfActorMessage::SetGatewayMacAddress { side, mac } => {
+                self.set_gateway_mac_address(side, mac);
+            }
+            BpfActorMessage::SetSourceMacAddress { side, mac } => {
+                self.set_source_mac_address(side, mac);
+            }
+            BpfActorMessage::SetIpOptionsPolicy { policy } => {
+                self.set_ip_options_policy(policy);
+            }
+            BpfActorMessage::SetUdpZeroChecksumPolicy { policy } => {
+                self.set_udp_zero_checksum_policy(policy);
+            }
+            BpfActorMessage::SetMinRoutingHeaderLen { len } => {
+                self.set_min_routing_header_len(len);
+            }
+            BpfActorMessage::SetTtlExpiredPolicy { policy } => {
+                self.set_ttl_expired_policy(policy);
+            }
+            BpfActorMessage::SetFragmentPolicy { policy } => {
+                self.set_fragment_policy(policy);
+            }
+            BpfActorMessage::AddBlocklistRules { rules, respond_to } => {
+                let _ = respond_to.send(self.add_blocklist_rules(&rules));
+            }
+            BpfActorMessage::RemoveBlocklistRules { rules, respond_to } => {
+                let _ = respond_to.send(self.remove_blocklist_rules(&rules));
+            }
+            BpfActorMessage::ListBlocklistRules { respond_to } => {
+                let _ = respond_to.send(self.list_blocklist_rules());
+            }
+            BpfActorMessage::AddBackendRemaps { remaps, respond_to } => {
+                let _ = respond_to.send(self.add_backend_remaps(&remaps));
+            }
+            BpfActorMessage::RemoveBackendRemaps {
+                old_backends,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.remove_backend_remaps(&old_backends));
+            }
+            BpfActorMessage::ListBackendRemaps { respond_to } => {
+                let _ = respond_to.send(self.list_backend_remaps());
+            }
+            BpfActorMessage::SetClientRateLimit { rate, burst } => {
+                self.set_client_rate_limit(rate, burst);
+            }
+            BpfActorMessage::SetPrefixRateLimit {
+                limited,
+                limit,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.set_prefix_rate_limit(limited, &limit));
+            }
+            BpfActorMessage::RemovePrefixRateLimit {
+                limited,
+                net,
+                prefix_len,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.remove_prefix_rate_limit(limited, net, prefix_len));
+            }
+            BpfActorMessage::ListPrefixRateLimits {
+                limited,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.list_prefix_rate_limits(limited));
+            }
+            BpfActorMessage::AddServiceBackends {
+                service_id,
+                pool,
+                backends,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.add_service_backends(service_id, pool, &backends));
+            }
+            BpfActorMessage::RemoveServiceBackends {
+                service_id,
+                pool,
+                backends,
+                respond_to,
+            } => {
+                let removed = self.remove_service_backends(service_id, pool, &backends);
+                let _ = respond_to.send(removed);
+            }
+            BpfActorMessage::SetServiceCanaryPercent {
+                service_id,
+                percent,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.set_service_canary_percent(service_id, percent));
+            }
+            BpfActorMessage::ListServices { respond_to } => {
+                let _ = respond_to.send(self.list_services());
+            }
+            BpfActorMessage::ReportProbes { results } => {
+                self.report_probes(&results);
+            }
+            BpfActorMessage::GetBackendHealth { respond_to } => {
+                let _ = respond_to.send(self.health.values().cloned().collect());
+            }
+            BpfActorMessage::SetBackendMaintenance {
+                addr,
+                maintenance,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.set_backend_maintenance(addr, maintenance));
+            }
+            BpfActorMessage::ListBackendMaintenance { respond_to } => {
+                let _ = respond_to.send(self.list_backend_maintenance());
+            }
+            BpfActorMessage::GetTopRateLimitedClients { limit, respond_to } => {
+                let _ = respond_to.send(self.get_top_rate_limited_clients(limit));
+            }
+            BpfActorMessage::SetHeaderAuthPolicy { policy } => {
+                self.set_header_auth_policy(policy);
+            }
+            BpfActorMessage::SetClientTokenPolicy { policy } => {
+                self.set_client_token_policy(policy);
+            }
+            BpfActorMessage::SetSeenClientWindow { window } => {
+                self.set_seen_client_window(window);
+            }
+            BpfActorMessage::SetFlowIdleTimeout { timeout } => {
+                self.set_flow_idle_timeout(timeout);
+            }
+            BpfActorMessage::ListFlows {
+                backend,
+                limit,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.list_flows(backend, limit));
+            }
+            BpfActorMessage::FlushFlows {
+                backend,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.flush_flows(backend));
+            }
+            BpfActorMessage::PinFlow { flow, respond_to } => {
+                let _ = respond_to.send(self.pin_flow(&flow));
+            }
+            BpfActorMessage::UnpinFlow {
+                client_ip,
+                client_port,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.unpin_flow(client_ip, client_port));
+            }
+            BpfActorMessage::SetTrafficClientPrefixLen { prefix_len } => {
+                self.set_traffic_client_prefix_len(prefix_len);
+            }
+            BpfActorMessage::ListTraffic {
+                keyed,
+                start,
+                limit,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.list_traffic(keyed, start, limit));
+            }
+            BpfActorMessage::GetTopTalkers { respond_to } => {
+                let _ = respond_to.send(self.top_talkers.clone());
+            }
+            BpfActorMessage::SetTopTalkerBlocking { blocking } => {
+                self.set_top_talker_blocking(blocking);
+            }
+            BpfActorMessage::SetMaxAmplification { ratio } => {
+                self.set_max_amplification(ratio);
+            }
+            BpfActorMessage::SetChallengePolicy { policy } => {
+                self.set_challenge_policy(policy);
+            }
+            BpfActorMessage::RotateKey { keyed, key } => {
+                self.rotate_key(keyed, &key);
+            }
+            BpfActorMessage::RetirePreviousKey { keyed } => {
+                self.retire_previous_key(keyed);
             }
         }
     }
@@ -151,12 +1192,124 @@ impl BpfActor {
         let total_packets = read_metric!(self.stats.total_packets);
         let client_to_server_packets = read_metric!(self.stats.client_to_server_packets);
         let server_to_client_packets = read_metric!(self.stats.server_to_client_packets);
+        let non_unicast_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_NON_UNICAST_DROPPED);
+        let ip_options_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_IP_OPTIONS_DROPPED);
+        let udp_checksum_added_packets =
+            read_metric!(self.stats.counters, COUNTER_UDP_CHECKSUM_ADDED);
+        let ip_length_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_IP_LENGTH_DROPPED);
+        let udp_length_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_UDP_LENGTH_DROPPED);
+        let short_routing_header_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_SHORT_ROUTING_HEADER_DROPPED);
+        let ttl_expired_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_TTL_EXPIRED_DROPPED);
+        let icmp_time_exceeded_sent_packets =
+            read_metric!(self.stats.counters, COUNTER_ICMP_TIME_EXCEEDED_SENT);
+        let fragments_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_FRAGMENTS_DROPPED);
+        let fragments_passed_packets = read_metric!(self.stats.counters, COUNTER_FRAGMENTS_PASSED);
+        let first_fragments_forwarded_packets =
+            read_metric!(self.stats.counters, COUNTER_FIRST_FRAGMENTS_FORWARDED);
+        let target_unspecified_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_TARGET_UNSPECIFIED_DROPPED);
+        let target_loopback_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_TARGET_LOOPBACK_DROPPED);
+        let target_multicast_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_TARGET_MULTICAST_DROPPED);
+        let target_broadcast_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_TARGET_BROADCAST_DROPPED);
+        let target_link_local_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_TARGET_LINK_LOCAL_DROPPED);
+        let target_local_network_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_TARGET_LOCAL_NETWORK_DROPPED);
+        let target_router_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_TARGET_ROUTER_DROPPED);
+        let rate_limited_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_RATE_LIMITED_DROPPED);
+        let blocklist_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_BLOCKLIST_DROPPED);
+        let backend_overload_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_BACKEND_OVERLOAD_DROPPED);
+        let backends = self.get_backend_stats();
+        let header_auth_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_HEADER_AUTH_DROPPED);
+        let client_token_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_CLIENT_TOKEN_DROPPED);
+        let unseen_client_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_UNSEEN_CLIENT_DROPPED);
+        let amplification_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_AMPLIFICATION_DROPPED);
+        let challenges_sent_packets = read_metric!(self.stats.counters, COUNTER_CHALLENGES_SENT);
+        let cookies_verified_packets = read_metric!(self.stats.counters, COUNTER_COOKIES_VERIFIED);
+        let cookie_dropped_packets = read_metric!(self.stats.counters, COUNTER_COOKIE_DROPPED);
+        let service_unavailable_dropped_packets =
+            read_metric!(self.stats.counters, COUNTER_SERVICE_UNAVAILABLE_DROPPED);
+        let unavailable_backend_skipped_packets =
+            read_metric!(self.stats.counters, COUNTER_UNAVAILABLE_BACKEND_SKIPPED);
+        let backend_remapped_packets = read_metric!(self.stats.counters, COUNTER_BACKEND_REMAPPED);
 
         RouterStatistics {
             total_packets,
             client_to_server_packets,
             server_to_client_packets,
+            non_unicast_dropped_packets,
+            ip_options_dropped_packets,
+            udp_checksum_added_packets,
+            ip_length_dropped_packets,
+            udp_length_dropped_packets,
+            short_routing_header_dropped_packets,
+            ttl_expired_dropped_packets,
+            icmp_time_exceeded_sent_packets,
+            fragments_dropped_packets,
+            fragments_passed_packets,
+            first_fragments_forwarded_packets,
+            target_unspecified_dropped_packets,
+            target_loopback_dropped_packets,
+            target_multicast_dropped_packets,
+            target_broadcast_dropped_packets,
+            target_link_local_dropped_packets,
+            target_local_network_dropped_packets,
+            target_router_dropped_packets,
+            rate_limited_dropped_packets,
+            blocklist_dropped_packets,
+            backend_overload_dropped_packets,
+            backends,
+            header_auth_dropped_packets,
+            client_token_dropped_packets,
+            unseen_client_dropped_packets,
+            amplification_dropped_packets,
+            challenges_sent_packets,
+            cookies_verified_packets,
+            cookie_dropped_packets,
+            service_unavailable_dropped_packets,
+            unavailable_backend_skipped_packets,
+            backend_remapped_packets,
+            unique_clients: self.unique_clients,
+        }
+    }
+
+    fn get_backend_stats(&self) -> Vec<BackendStatistics> {
+        let mut backends: BTreeMap<u32, BackendStatistics> = BTreeMap::new();
+        for (addr, bucket) in self.stats.backend_buckets.iter().filter_map(|item| item.ok()) {
+            let backend = backends.entry(addr).or_default();
+            backend.addr = addr;
+            backend.shed_packets = bucket.dropped;
         }
+        for (addr, packets) in self
+            .stats
+            .service_backend_packets
+            .iter()
+            .filter_map(|item| item.ok())
+        {
+            let backend = backends.entry(addr).or_default();
+            backend.addr = addr;
+            backend.service_packets = packets.iter().sum();
+        }
+
+        backends.into_values().collect()
     }
 
     fn set_local_net_mask(&mut self, net: u32, mask: u32) {
@@ -173,15 +1326,849 @@ impl BpfActor {
         println!("Setting backend network to {:#04x} and mask to {:#04x} ", net, mask);
     }
 
-    fn set_gateway_mac_address(&mut self, mac: u64) {
-        write_map!(self.configs.gateway_mac_address, 0, mac);
-        println!("Setting gateway MAC address to to {:#04x}", mac);
+    fn set_gateway_mac_address(&mut self, side: u32, mac: u64) {
+        write_map!(self.configs.gateway_mac_address, side, mac);
+        println!("Setting gateway MAC address of side {} to {:#04x}", side, mac);
+    }
+
+    fn set_source_mac_address(&mut self, side: u32, mac: u64) {
+        write_map!(self.configs.source_mac_address, side, mac);
+        println!("Setting source MAC address of side {} to {:#04x}", side, mac);
+    }
+
+    fn set_ip_options_policy(&mut self, policy: u64) {
+        write_map!(self.configs.config, CONFIG_IP_OPTIONS_POLICY, policy);
+        println!("Setting IP options policy to {}", policy);
+    }
+
+    fn set_udp_zero_checksum_policy(&mut self, policy: u64) {
+        write_map!(self.configs.config, CONFIG_UDP_ZERO_CHECKSUM_POLICY, policy);
+        println!("Setting UDP zero checksum policy to {}", policy);
+    }
+
+    fn set_min_routing_header_len(&mut self, len: u32) {
+        write_map!(self.configs.config, CONFIG_MIN_ROUTING_HEADER_LEN, len as u64);
+        println!("Setting minimum routing header length to {}", len);
+    }
+
+    fn set_ttl_expired_policy(&mut self, policy: u64) {
+        write_map!(self.configs.config, CONFIG_TTL_EXPIRED_POLICY, policy);
+        println!("Setting TTL expired policy to {}", policy);
+    }
+
+    fn set_fragment_policy(&mut self, policy: u64) {
+        write_map!(self.configs.config, CONFIG_FRAGMENT_POLICY, policy);
+        println!("Setting fragment policy to {}", policy);
+    }
+
+    // Adding a prefix that is already blocked updates its expiry and keeps its hits
+    fn add_blocklist_rules(&mut self, rules: &[BlocklistRule]) -> Result<(), MapError> {
+        let now = monotonic_time();
+
+        for rule in rules {
+            let key = Key::new(rule.prefix_len, rule.net.to_be());
+            let hits = match self.configs.blocklist.get(&key, 0) {
+                Ok(entry) => entry.hits,
+                Err(_) => 0,
+            };
+            let expires_at = expires_at(now, rule.expires_in)?;
+
+            let entry = BlocklistEntry { expires_at, hits };
+            self.configs.blocklist.insert(&key, entry, 0)?;
+        }
+
+        println!("Added {} prefixes to blocklist", rules.len());
+        Ok(())
+    }
+
+    fn remove_blocklist_rules(&mut self, rules: &[BlocklistRule]) -> usize {
+        let mut removed = 0;
+        for rule in rules {
+            let key = Key::new(rule.prefix_len, rule.net.to_be());
+            if self.configs.blocklist.remove(&key).is_ok() {
+                removed += 1;
+            }
+        }
+
+        println!("Removed {} prefixes from blocklist", removed);
+        removed
+    }
+
+    fn list_blocklist_rules(&self) -> Vec<BlocklistRule> {
+        let now = monotonic_time().as_nanos() as u64;
+
+        let mut rules = Vec::new();
+        for item in self.configs.blocklist.iter() {
+            let Ok((key, entry)) = item else {
+                continue;
+            };
+            let expires_in = match entry.expires_at {
+                0 => None,
+                expires_at if expires_at <= now => continue,
+                expires_at => Some(Duration::from_nanos(expires_at - now)),
+            };
+
+            rules.push(BlocklistRule {
+                net: u32::from_be(key.data()),
+                prefix_len: key.prefix_len(),
+                expires_in,
+                hits: entry.hits,
+            });
+        }
+
+        rules
+    }
+
+    fn add_backend_remaps(&mut self, remaps: &[Remap]) -> Result<(), MapError> {
+        let now = monotonic_time();
+
+        for remap in remaps {
+            let hits = match self.configs.backend_remaps.get(&remap.old_backend, 0) {
+                Ok(entry) => entry.hits,
+                Err(_) => 0,
+            };
+            let expires_at = expires_at(now, remap.expires_in)?;
+
+            let entry = BackendRemap {
+                expires_at,
+                hits,
+                backend: remap.new_backend,
+                padding: 0,
+            };
+            self.configs
+                .backend_remaps
+                .insert(remap.old_backend, entry, 0)?;
+        }
+
+        println!("Added {} backend remaps", remaps.len());
+        Ok(())
+    }
+
+    fn remove_backend_remaps(&mut self, old_backends: &[u32]) -> usize {
+        let removed = old_backends
+            .iter()
+            .filter(|&old_backend| self.configs.backend_remaps.remove(old_backend).is_ok())
+            .count();
+
+        println!("Removed {} backend remaps", removed);
+        removed
+    }
+
+    fn list_backend_remaps(&self) -> Vec<Remap> {
+        let now = monotonic_time().as_nanos() as u64;
+
+        let mut remaps = Vec::new();
+        for item in self.configs.backend_remaps.iter() {
+            let Ok((old_backend, entry)) = item else {
+                continue;
+            };
+            let expires_in = match entry.expires_at {
+                0 => None,
+                expires_at if expires_at <= now => continue,
+                expires_at => Some(Duration::from_nanos(expires_at - now)),
+            };
+
+            remaps.push(Remap {
+                old_backend,
+                new_backend: entry.backend,
+                expires_in,
+                hits: entry.hits,
+            });
+        }
+
+        remaps
+    }
+
+    fn set_client_rate_limit(&mut self, rate: u64, burst: u64) {
+        write_map!(self.configs.config, CONFIG_CLIENT_RATE_LIMIT, rate);
+        write_map!(self.configs.config, CONFIG_CLIENT_BURST, burst);
+        println!("Setting client rate limit to {} pps with burst {}", rate, burst);
+    }
+
+    fn rate_limits(&self, limited: RateLimited) -> &LpmTrie<MapData, u32, RateLimit> {
+        match limited {
+            RateLimited::Clients => &self.configs.client_rate_limits,
+            RateLimited::Backends => &self.configs.backend_rate_limits,
+        }
+    }
+
+    fn rate_limits_mut(&mut self, limited: RateLimited) -> &mut LpmTrie<MapData, u32, RateLimit> {
+        match limited {
+            RateLimited::Clients => &mut self.configs.client_rate_limits,
+            RateLimited::Backends => &mut self.configs.backend_rate_limits,
+        }
+    }
+
+    fn set_prefix_rate_limit(
+        &mut self,
+        limited: RateLimited,
+        limit: &PrefixRateLimit,
+    ) -> Result<(), MapError> {
+        let key = Key::new(limit.prefix_len, limit.net.to_be());
+        let value = RateLimit {
+            rate: limit.rate,
+            burst: limit.burst,
+        };
+        self.rate_limits_mut(limited).insert(&key, value, 0)?;
+
+        println!(
+            "Setting rate limit of {:?} in {:#04x}/{} to {} pps with burst {}",
+            limited, limit.net, limit.prefix_len, limit.rate, limit.burst
+        );
+        Ok(())
+    }
+
+    fn remove_prefix_rate_limit(
+        &mut self,
+        limited: RateLimited,
+        net: u32,
+        prefix_len: u32,
+    ) -> bool {
+        let key = Key::new(prefix_len, net.to_be());
+        let removed = self.rate_limits_mut(limited).remove(&key).is_ok();
+
+        println!("Removing rate limit of {:?} in {:#04x}/{}", limited, net, prefix_len);
+        removed
+    }
+
+    fn list_prefix_rate_limits(&self, limited: RateLimited) -> Vec<PrefixRateLimit> {
+        self.rate_limits(limited)
+            .iter()
+            .filter_map(|item| item.ok())
+            .map(|(key, value)| PrefixRateLimit {
+                net: u32::from_be(key.data()),
+                prefix_len: key.prefix_len(),
+                rate: value.rate,
+                burst: value.burst,
+            })
+            .collect()
+    }
+
+    // Returns false, leaving the pool as it is, if it would have more than
+    // MAX_SERVICE_BACKENDS backends
+    fn add_service_backends(&mut self, service_id: u32, pool: Pool, backends: &[u32]) -> bool {
+        let mut pools = self.services.get(&service_id).cloned().unwrap_or_default();
+        let members = pools.pool_mut(pool);
+        members.extend(backends);
+        if members.len() > MAX_SERVICE_BACKENDS {
+            return false;
+        }
+
+        println!(
+            "Service {} has {} members in {:?}",
+            service_id,
+            members.len(),
+            pool
+        );
+        self.services.insert(service_id, pools);
+        self.write_service_table(service_id);
+        true
+    }
+
+    fn remove_service_backends(&mut self, service_id: u32, pool: Pool, backends: &[u32]) -> usize {
+        let Some(pools) = self.services.get_mut(&service_id) else {
+            return 0;
+        };
+        let members = pools.pool_mut(pool);
+        let removed = backends
+            .iter()
+            .filter(|&backend| members.remove(backend))
+            .count();
+        if pools.backends.is_empty() && pools.canaries.is_empty() {
+            self.services.remove(&service_id);
+        }
+
+        println!(
+            "Removed {} members of {:?} from service {}",
+            removed, pool, service_id
+        );
+        self.write_service_table(service_id);
+
+        // Clients of removed backends move to the remaining ones right away
+        self.remove_flows(|affinity| {
+            affinity.service_id as u32 == service_id
+                && affinity.pinned == 0
+                && backends.contains(&affinity.backend)
+        });
+        removed
+    }
+
+    // Returns false if the service has no backends
+    fn set_service_canary_percent(&mut self, service_id: u32, percent: u32) -> bool {
+        let Some(pools) = self.services.get_mut(&service_id) else {
+            return false;
+        };
+        pools.canary_percent = percent;
+
+        println!("Sending {}% of service {} to canaries", percent, service_id);
+        self.write_service_table(service_id);
+        true
+    }
+
+    fn list_services(&self) -> Vec<Service> {
+        self.services
+            .iter()
+            .map(|(&id, pools)| Service {
+                id,
+                backends: pools.backends.iter().copied().collect(),
+                canaries: pools.canaries.iter().copied().collect(),
+                canary_percent: pools.canary_percent,
+            })
+            .collect()
+    }
+
+    // Rebuilds the lookup table of a service from its pools. Packets looked up while the
+    // table is written may go to either the old or the new backend of their entry.
+    fn write_service_table(&mut self, service_id: u32) {
+        let pools = self.services.get(&service_id).cloned().unwrap_or_default();
+        let backends: Vec<u32> = pools.backends.into_iter().collect();
+        let canaries: Vec<u32> = pools.canaries.into_iter().collect();
+        let mut table = [0; MAGLEV_TABLE_SIZE];
+        maglev::populate_with_canaries(&backends, &canaries, pools.canary_percent, &mut table);
+
+        let base = service_id * MAGLEV_TABLE_SIZE as u32;
+        for (entry, backend) in table.into_iter().enumerate() {
+            write_map!(self.configs.service_tables, base + entry as u32, backend);
+        }
+    }
+
+    // Updates the health of backends with the results of a round of probes. Backends
+    // missing from the round are no longer checked and are forgotten.
+    fn report_probes(&mut self, results: &[ProbeResult]) {
+        let probed: BTreeSet<u32> = results.iter().map(|result| result.addr).collect();
+        let forgotten: Vec<u32> = self
+            .health
+            .keys()
+            .filter(|addr| !probed.contains(addr))
+            .copied()
+            .collect();
+        for addr in forgotten {
+            self.health.remove(&addr);
+            if let Err(e) = self.write_backend_state(addr) {
+                println!("Failed to update backend {}: {}", Ipv4Addr::from_bits(addr), e);
+            }
+        }
+
+        for result in results {
+            let health = self.health.entry(result.addr).or_insert(BackendHealth {
+                addr: result.addr,
+                up: true,
+                ..Default::default()
+            });
+            match result.rtt {
+                Some(rtt) => {
+                    health.probes_succeeded += 1;
+                    health.last_rtt = Some(rtt);
+                }
+                None => health.probes_failed += 1,
+            }
+
+            if result.rtt.is_some() == health.up {
+                health.streak = 0;
+                continue;
+            }
+            health.streak += 1;
+            let threshold = match health.up {
+                true => UNHEALTHY_THRESHOLD,
+                false => HEALTHY_THRESHOLD,
+            };
+            if health.streak < threshold {
+                continue;
+            }
+
+            health.up = !health.up;
+            health.streak = 0;
+            println!(
+                "Backend {} is {}",
+                Ipv4Addr::from_bits(result.addr),
+                if health.up { "up" } else { "down" }
+            );
+            if let Err(e) = self.write_backend_state(result.addr) {
+                println!("Failed to update backend {}: {}", Ipv4Addr::from_bits(result.addr), e);
+            }
+        }
+    }
+
+    // Takes a backend out of services for maintenance, or puts it back if None. The
+    // previous state is kept if the backend state map is full.
+    fn set_backend_maintenance(
+        &mut self,
+        addr: u32,
+        maintenance: Option<Maintenance>,
+    ) -> Result<(), MapError> {
+        let previous = match maintenance {
+            Some(maintenance) => self.maintenance.insert(addr, maintenance),
+            None => self.maintenance.remove(&addr),
+        };
+        if let Err(e) = self.write_backend_state(addr) {
+            match previous {
+                Some(previous) => self.maintenance.insert(addr, previous),
+                None => self.maintenance.remove(&addr),
+            };
+            return Err(e);
+        }
+
+        println!(
+            "Backend {} is {}",
+            Ipv4Addr::from_bits(addr),
+            match maintenance {
+                Some(Maintenance::Draining { .. }) => "draining",
+                Some(Maintenance::Disabled) => "disabled",
+                None => "enabled",
+            }
+        );
+        Ok(())
+    }
+
+    fn list_backend_maintenance(&self) -> Vec<(u32, Maintenance)> {
+        self.maintenance
+            .iter()
+            .map(|(&addr, &maintenance)| (addr, maintenance))
+            .collect()
+    }
+
+    // Disables draining backends whose drain timeout has passed
+    fn disable_drained_backends(&mut self) {
+        let now = Instant::now();
+        let drained: Vec<u32> = self
+            .maintenance
+            .iter()
+            .filter(|(_, maintenance)| {
+                matches!(maintenance, Maintenance::Draining { until } if *until <= now)
+            })
+            .map(|(&addr, _)| addr)
+            .collect();
+        for addr in drained {
+            if let Err(e) = self.set_backend_maintenance(addr, Some(Maintenance::Disabled)) {
+                println!("Failed to disable backend {}: {}", Ipv4Addr::from_bits(addr), e);
+            }
+        }
+    }
+
+    // Writes the state of a backend the XDP program acts on. Backends that are down are
+    // skipped even by the clients of a draining backend.
+    fn write_backend_state(&mut self, addr: u32) -> Result<(), MapError> {
+        let down = self.health.get(&addr).is_some_and(|health| !health.up);
+        let state = match self.maintenance.get(&addr) {
+            Some(Maintenance::Disabled) => BACKEND_DISABLED,
+            _ if down => BACKEND_DOWN,
+            Some(Maintenance::Draining { .. }) => BACKEND_DRAINING,
+            None => BACKEND_UP,
+        };
+
+        match state {
+            BACKEND_UP => {
+                let _ = self.configs.backend_states.remove(&addr);
+                Ok(())
+            }
+            _ => self.configs.backend_states.insert(addr, state, 0),
+        }
+    }
+
+    // Returns the addresses and dropped packets of the clients with the most packets
+    // dropped, or all clients with dropped packets if the limit is zero
+    fn get_top_rate_limited_clients(&self, limit: usize) -> Vec<(u32, u64)> {
+        let mut clients: Vec<(u32, u64)> = self
+            .stats
+            .client_buckets
+            .iter()
+            .filter_map(|item| item.ok())
+            .filter(|(_, bucket)| bucket.dropped > 0)
+            .map(|(addr, bucket)| (addr, bucket.dropped))
+            .collect();
+
+        clients.sort_by_key(|&(_, dropped)| Reverse(dropped));
+        if limit > 0 {
+            clients.truncate(limit);
+        }
+        clients
+    }
+
+    fn set_header_auth_policy(&mut self, policy: u64) {
+        write_map!(self.configs.config, CONFIG_HEADER_AUTH_POLICY, policy);
+        println!("Setting header authentication policy to {}", policy);
+    }
+
+    fn set_client_token_policy(&mut self, policy: u64) {
+        write_map!(self.configs.config, CONFIG_CLIENT_TOKEN_POLICY, policy);
+        println!("Setting client token policy to {}", policy);
+    }
+
+    fn set_seen_client_window(&mut self, window: Duration) {
+        let window_ns = window.as_nanos() as u64;
+        write_map!(self.configs.config, CONFIG_SEEN_CLIENT_WINDOW, window_ns);
+        println!("Setting seen client window to {:?}", window);
+    }
+
+    fn set_flow_idle_timeout(&mut self, timeout: Duration) {
+        let timeout_ns = timeout.as_nanos() as u64;
+        write_map!(self.configs.config, CONFIG_FLOW_IDLE_TIMEOUT, timeout_ns);
+        println!("Setting flow idle timeout to {:?}", timeout);
+    }
+
+    // Returns the flows sent to the backend, or to any backend if None, up to the limit
+    // unless it is zero
+    fn list_flows(&self, backend: Option<u32>, limit: usize) -> Vec<Flow> {
+        let now = monotonic_time().as_nanos() as u64;
+        let flows = self
+            .configs
+            .service_flows
+            .iter()
+            .filter_map(|item| item.ok())
+            .filter(|(_, affinity)| backend.is_none_or(|backend| affinity.backend == backend))
+            .map(|(key, affinity)| Flow {
+                client_ip: (key >> 16) as u32,
+                client_port: key as u16,
+                service_id: affinity.service_id as u32,
+                backend: affinity.backend,
+                idle: Duration::from_nanos(now.saturating_sub(affinity.last_seen)),
+                pinned: affinity.pinned != 0,
+            });
+
+        match limit {
+            0 => flows.collect(),
+            limit => flows.take(limit).collect(),
+        }
+    }
+
+    // Removes the flows matching the filter, returning how many were removed
+    fn remove_flows(&mut self, filter: impl Fn(&FlowAffinity) -> bool) -> usize {
+        let keys: Vec<u64> = self
+            .configs
+            .service_flows
+            .iter()
+            .filter_map(|item| item.ok())
+            .filter(|(_, affinity)| filter(affinity))
+            .map(|(key, _)| key)
+            .collect();
+        for key in keys.iter() {
+            let _ = self.configs.service_flows.remove(key);
+        }
+
+        keys.len()
+    }
+
+    // Removes the flows sent to the backend, or to any backend if None, except pinned ones
+    fn flush_flows(&mut self, backend: Option<u32>) -> usize {
+        let flushed = self.remove_flows(|affinity| {
+            affinity.pinned == 0 && backend.is_none_or(|backend| affinity.backend == backend)
+        });
+
+        println!("Flushed {} flows", flushed);
+        flushed
+    }
+
+    fn pin_flow(&mut self, flow: &Flow) -> Result<(), MapError> {
+        let affinity = FlowAffinity {
+            backend: flow.backend,
+            service_id: flow.service_id as u16,
+            pinned: 1,
+            last_seen: monotonic_time().as_nanos() as u64,
+        };
+        let key = flow_key(flow.client_ip, flow.client_port);
+        self.configs.service_flows.insert(key, affinity, 0)?;
+
+        println!(
+            "Pinned flow {}:{} of service {} to {}",
+            Ipv4Addr::from_bits(flow.client_ip),
+            flow.client_port,
+            flow.service_id,
+            Ipv4Addr::from_bits(flow.backend)
+        );
+        Ok(())
+    }
+
+    // Returns false if the flow is not pinned
+    fn unpin_flow(&mut self, client_ip: u32, client_port: u16) -> bool {
+        let key = flow_key(client_ip, client_port);
+        match self.configs.service_flows.get(&key, 0) {
+            Ok(affinity) if affinity.pinned != 0 => {
+                let _ = self.configs.service_flows.remove(&key);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Client prefixes counted with the previous length would overlap the new ones, so
+    // their counters are cleared
+    fn set_traffic_client_prefix_len(&mut self, prefix_len: u32) {
+        write_map!(self.configs.config, CONFIG_TRAFFIC_CLIENT_PREFIX_LEN, prefix_len as u64);
+
+        let prefixes: Vec<u32> = self
+            .stats
+            .client_prefix_traffic
+            .keys()
+            .filter_map(|item| item.ok())
+            .collect();
+        for prefix in prefixes.iter() {
+            let _ = self.stats.client_prefix_traffic.remove(prefix);
+        }
+        println!("Setting traffic client prefix length to {}", prefix_len);
+    }
+
+    fn traffic_counters(
+        &self,
+        keyed: TrafficKeyed,
+    ) -> &PerCpuHashMap<MapData, u32, TrafficCounter> {
+        match keyed {
+            TrafficKeyed::Backends => &self.stats.backend_traffic,
+            TrafficKeyed::ClientPrefixes => &self.stats.client_prefix_traffic,
+        }
+    }
+
+    // Returns the counters from the start address on in order of address, up to the limit
+    // unless it is zero. Counters are summed over all CPUs.
+    fn list_traffic(&self, keyed: TrafficKeyed, start: u32, limit: usize) -> TrafficPage {
+        let prefix_len = match keyed {
+            TrafficKeyed::Backends => 32,
+            TrafficKeyed::ClientPrefixes => {
+                let config = &self.configs.config;
+                let prefix_len = config.get(&CONFIG_TRAFFIC_CLIENT_PREFIX_LEN, 0).unwrap_or(0);
+                prefix_len.min(32) as u32
+            }
+        };
+        let now = monotonic_time().as_nanos() as u64;
+
+        let mut traffic: Vec<Traffic> = self
+            .traffic_counters(keyed)
+            .iter()
+            .filter_map(|item| item.ok())
+            .filter(|&(addr, _)| addr >= start)
+            .map(|(addr, counters)| {
+                let mut total = Traffic {
+                    addr,
+                    prefix_len,
+                    requests: 0,
+                    request_bytes: 0,
+                    responses: 0,
+                    response_bytes: 0,
+                    idle: Duration::ZERO,
+                };
+                let mut last_seen = 0;
+                for counter in counters.iter() {
+                    total.requests += counter.requests;
+                    total.request_bytes += counter.request_bytes;
+                    total.responses += counter.responses;
+                    total.response_bytes += counter.response_bytes;
+                    last_seen = last_seen.max(counter.last_seen);
+                }
+                total.idle = Duration::from_nanos(now.saturating_sub(last_seen));
+                total
+            })
+            .collect();
+        traffic.sort_by_key(|counter| counter.addr);
+
+        let mut next = None;
+        if limit > 0 && traffic.len() > limit {
+            traffic.truncate(limit);
+            next = traffic.last().and_then(|counter| counter.addr.checked_add(1));
+        }
+        TrafficPage { traffic, next }
+    }
+
+    // Removes the counters of backends and client prefixes without packets for
+    // TRAFFIC_IDLE_TIMEOUT, so that new ones can be counted. Packets counted between
+    // reading and removing an entry are lost.
+    fn remove_idle_traffic(&mut self) {
+        let now = monotonic_time().as_nanos() as u64;
+        let timeout = TRAFFIC_IDLE_TIMEOUT.as_nanos() as u64;
+
+        for keyed in [TrafficKeyed::Backends, TrafficKeyed::ClientPrefixes] {
+            let idle: Vec<u32> = self
+                .traffic_counters(keyed)
+                .iter()
+                .filter_map(|item| item.ok())
+                .filter(|(_, counters)| {
+                    let last_seen = counters.iter().map(|counter| counter.last_seen).max();
+                    now.saturating_sub(last_seen.unwrap_or(0)) >= timeout
+                })
+                .map(|(addr, _)| addr)
+                .collect();
+            let counters = match keyed {
+                TrafficKeyed::Backends => &mut self.stats.backend_traffic,
+                TrafficKeyed::ClientPrefixes => &mut self.stats.client_prefix_traffic,
+            };
+            for addr in idle.iter() {
+                let _ = counters.remove(addr);
+            }
+        }
+    }
+
+    fn set_top_talker_blocking(&mut self, blocking: Option<TopTalkerBlocking>) {
+        self.top_talker_blocking = blocking;
+        match blocking {
+            Some(blocking) => println!(
+                "Blocking clients above {} packets per second for {:?}",
+                blocking.rate, blocking.duration
+            ),
+            None => println!("Not blocking top talkers"),
+        }
+    }
+
+    // Replaces the top talkers with those of the window that just ended, clearing the
+    // sketches for the next window, and blocks clients sending above the blocking rate
+    fn rotate_heavy_hitters(&mut self) {
+        self.top_talkers = TopTalkers {
+            sources: self.take_heavy_hitters(SKETCH_SOURCES),
+            targets: self.take_heavy_hitters(SKETCH_TARGETS),
+            window: HEAVY_HITTER_WINDOW,
+        };
+
+        let Some(blocking) = self.top_talker_blocking else {
+            return;
+        };
+        let threshold = blocking.rate.saturating_mul(HEAVY_HITTER_WINDOW.as_secs());
+        let rules: Vec<BlocklistRule> = self
+            .top_talkers
+            .sources
+            .iter()
+            .filter(|talker| talker.packets > threshold)
+            .map(|talker| BlocklistRule {
+                net: talker.addr,
+                prefix_len: 32,
+                expires_in: Some(blocking.duration),
+                hits: 0,
+            })
+            .collect();
+        if !rules.is_empty() {
+            if let Err(e) = self.add_blocklist_rules(&rules) {
+                warn!("Failed to block top talkers: {}", e);
+            }
+        }
+    }
+
+    // Returns the candidates of the sketches of all CPUs in descending order of their
+    // packets estimated from the merged sketches, and clears the sketches. Packets counted
+    // between reading and clearing the sketches are lost.
+    fn take_heavy_hitters(&mut self, index: u32) -> Vec<TopTalker> {
+        let Ok(sketches) = self.stats.heavy_hitters.get(&index, 0) else {
+            return Vec::new();
+        };
+        let candidates: BTreeSet<u32> = sketches
+            .iter()
+            .flat_map(|sketch| sketch.candidates.iter())
+            .filter(|candidate| candidate.packets != 0)
+            .map(|candidate| candidate.addr)
+            .collect();
+        let mut talkers: Vec<TopTalker> = candidates
+            .into_iter()
+            .map(|addr| TopTalker {
+                addr,
+                packets: sketch::estimate(&sketches, addr),
+            })
+            .collect();
+        talkers.sort_by_key(|talker| Reverse(talker.packets));
+        talkers.truncate(SKETCH_CANDIDATES);
+
+        let empty = vec![HeavyHitterSketch::EMPTY; sketches.len()];
+        if let Ok(empty) = PerCpuValues::try_from(empty) {
+            let _ = self.stats.heavy_hitters.set(index, empty, 0);
+        }
+        talkers
+    }
+
+    // Estimates the clients of the window that just ended from the registers of all CPUs,
+    // and clears the registers for the next window. Clients recorded between reading and
+    // clearing the registers are lost.
+    fn rotate_unique_clients(&mut self) {
+        let Ok(values) = self.stats.client_registers.get(&0, 0) else {
+            return;
+        };
+        let mut merged = ClientRegisters::EMPTY;
+        for registers in values.iter() {
+            hyperloglog::merge(&mut merged, registers);
+        }
+        self.unique_clients = hyperloglog::estimate(&merged);
+
+        let empty = vec![ClientRegisters::EMPTY; values.len()];
+        if let Ok(empty) = PerCpuValues::try_from(empty) {
+            let _ = self.stats.client_registers.set(0, empty, 0);
+        }
+    }
+
+    fn set_max_amplification(&mut self, ratio: u64) {
+        write_map!(self.configs.config, CONFIG_MAX_AMPLIFICATION, ratio);
+        println!("Setting max amplification to {}", ratio);
+    }
+
+    fn set_challenge_policy(&mut self, policy: u64) {
+        write_map!(self.configs.config, CONFIG_CHALLENGE_POLICY, policy);
+        println!("Setting challenge policy to {}", policy);
+    }
+
+    fn keys_mut(&mut self, keyed: Keyed) -> &mut Array<MapData, SipKey> {
+        match keyed {
+            Keyed::HeaderTags => &mut self.configs.header_keys,
+            Keyed::ClientTokens => &mut self.configs.client_token_keys,
+            Keyed::Cookies => &mut self.configs.cookie_keys,
+        }
+    }
+
+    // The current key stays accepted as the previous key, until the values made with it
+    // are no longer in use and it is retired
+    fn rotate_key(&mut self, keyed: Keyed, key: &[u8; KEY_LEN]) {
+        let keys = self.keys_mut(keyed);
+        if let Ok(current) = keys.get(&KEY_CURRENT, 0) {
+            write_map!(keys, KEY_PREVIOUS, current);
+        }
+
+        let (k0, k1) = key_words(key);
+        let key = SipKey { k0, k1, active: 1 };
+        write_map!(keys, KEY_CURRENT, key);
+        println!("Rotated key of {:?}", keyed);
+    }
+
+    fn retire_previous_key(&mut self, keyed: Keyed) {
+        let key = SipKey {
+            k0: 0,
+            k1: 0,
+            active: 0,
+        };
+        write_map!(self.keys_mut(keyed), KEY_PREVIOUS, key);
+        println!("Retired previous key of {:?}", keyed);
+    }
+
+    // Removes expired map entries. The XDP program ignores them, but they would
+    // otherwise shadow shorter prefixes and take up space.
+    fn remove_expired_entries(&mut self) {
+        let now = monotonic_time().as_nanos() as u64;
+
+        let expired: Vec<Key<u32>> = self
+            .configs
+            .blocklist
+            .iter()
+            .filter_map(|item| item.ok())
+            .filter(|(_, entry)| entry.expires_at != 0 && entry.expires_at <= now)
+            .map(|(key, _)| key)
+            .collect();
+        for key in expired.iter() {
+            let _ = self.configs.blocklist.remove(key);
+        }
+        if !expired.is_empty() {
+            println!("Removed {} expired prefixes from blocklist", expired.len());
+        }
+
+        let expired: Vec<u32> = self
+            .configs
+            .backend_remaps
+            .iter()
+            .filter_map(|item| item.ok())
+            .filter(|(_, entry)| entry.expires_at != 0 && entry.expires_at <= now)
+            .map(|(old_backend, _)| old_backend)
+            .collect();
+        for old_backend in expired.iter() {
+            let _ = self.configs.backend_remaps.remove(old_backend);
+        }
+        if !expired.is_empty() {
+            println!("Removed {} expired backend remaps", expired.len());
+        }
     }
 }
 
 async fn run_actor(receiver: mpsc::Receiver<BpfActorMessage>, opt: Opt) {
     println!("Loading XDP program from file: {}", opt.bpf_prog);
-    let mut bpf = Ebpf::load_file(opt.bpf_prog).unwrap();
+    let mut bpf = Ebpf::load_file(&opt.bpf_prog).unwrap();
 
     if let Err(e) = EbpfLogger::init(&mut bpf) {
         warn!("Failed to initialize eBPF logger: {}", e);
@@ -190,54 +2177,202 @@ async fn run_actor(receiver: mpsc::Receiver<BpfActorMessage>, opt: Opt) {
     let program: &mut Xdp = bpf.program_mut("udp_router").unwrap().try_into().unwrap();
     program.load().expect("Failed to load XDP program");
 
+    attach_program(program, &opt.iface, &opt);
+    if let Some(backend_iface) = &opt.backend_iface {
+        attach_program(program, backend_iface, &opt);
+
+        let mut redirect_interfaces =
+            DevMap::try_from(bpf.map_mut("REDIRECT_INTERFACES").unwrap()).unwrap();
+        redirect_interfaces
+            .set(CLIENT_INTERFACE, interface_index(&opt.iface), None, 0)
+            .unwrap();
+        redirect_interfaces
+            .set(BACKEND_INTERFACE, interface_index(backend_iface), None, 0)
+            .unwrap();
+        println!(
+            "Redirecting traffic between interfaces {} and {}",
+            opt.iface, backend_iface
+        );
+    }
+
+    let stats = StatsMaps {
+        total_packets: PerCpuArray::try_from(bpf.take_map("TOTAL_PACKETS").unwrap()).unwrap(),
+        client_to_server_packets: PerCpuArray::try_from(
+            bpf.take_map("CLIENT_TO_SERVER_PACKETS").unwrap(),
+        )
+        .unwrap(),
+        server_to_client_packets: PerCpuArray::try_from(
+            bpf.take_map("SERVER_TO_CLIENT_PACKETS").unwrap(),
+        )
+        .unwrap(),
+        counters: PerCpuArray::try_from(bpf.take_map("COUNTERS").unwrap()).unwrap(),
+        client_buckets: HashMap::try_from(bpf.take_map("CLIENT_BUCKETS").unwrap()).unwrap(),
+        backend_buckets: HashMap::try_from(bpf.take_map("BACKEND_BUCKETS").unwrap()).unwrap(),
+        service_backend_packets: PerCpuHashMap::try_from(
+            bpf.take_map("SERVICE_BACKEND_PACKETS").unwrap(),
+        )
+        .unwrap(),
+        backend_traffic: PerCpuHashMap::try_from(bpf.take_map("BACKEND_TRAFFIC").unwrap())
+            .unwrap(),
+        client_prefix_traffic: PerCpuHashMap::try_from(
+            bpf.take_map("CLIENT_PREFIX_TRAFFIC").unwrap(),
+        )
+        .unwrap(),
+        heavy_hitters: PerCpuArray::try_from(bpf.take_map("HEAVY_HITTERS").unwrap()).unwrap(),
+        client_registers: PerCpuArray::try_from(bpf.take_map("CLIENT_REGISTERS").unwrap())
+            .unwrap(),
+    };
+
+    let configs = ConfigMaps {
+        local_net_and_mask: Array::try_from(bpf.take_map("LOCAL_NET_AND_MASK").unwrap()).unwrap(),
+        backend_net_and_mask: Array::try_from(bpf.take_map("BACKEND_NET_AND_MASK").unwrap())
+            .unwrap(),
+        gateway_mac_address: Array::try_from(bpf.take_map("GATEWAY_MAC_ADDRESS").unwrap()).unwrap(),
+        source_mac_address: Array::try_from(bpf.take_map("SOURCE_MAC_ADDRESS").unwrap()).unwrap(),
+        config: Array::try_from(bpf.take_map("CONFIG").unwrap()).unwrap(),
+        blocklist: LpmTrie::try_from(bpf.take_map("BLOCKLIST").unwrap()).unwrap(),
+        client_rate_limits: LpmTrie::try_from(bpf.take_map("CLIENT_RATE_LIMITS").unwrap())
+            .unwrap(),
+        backend_rate_limits: LpmTrie::try_from(bpf.take_map("BACKEND_RATE_LIMITS").unwrap())
+            .unwrap(),
+        header_keys: Array::try_from(bpf.take_map("HEADER_KEYS").unwrap()).unwrap(),
+        client_token_keys: Array::try_from(bpf.take_map("CLIENT_TOKEN_KEYS").unwrap()).unwrap(),
+        cookie_keys: Array::try_from(bpf.take_map("COOKIE_KEYS").unwrap()).unwrap(),
+        service_tables: Array::try_from(bpf.take_map("SERVICE_TABLES").unwrap()).unwrap(),
+        backend_states: HashMap::try_from(bpf.take_map("BACKEND_STATES").unwrap()).unwrap(),
+        service_flows: HashMap::try_from(bpf.take_map("SERVICE_FLOWS").unwrap()).unwrap(),
+        backend_remaps: HashMap::try_from(bpf.take_map("BACKEND_REMAPS").unwrap()).unwrap(),
+    };
+
+    let mut actor = BpfActor::new(receiver, stats, configs);
+
+    // Forwarded packets use the MAC address of their egress interface as source
+    actor.set_source_mac_address(CLIENT_INTERFACE, interface_mac_address(&opt.iface));
+    if let Some(backend_iface) = &opt.backend_iface {
+        actor.set_source_mac_address(BACKEND_INTERFACE, interface_mac_address(backend_iface));
+    }
+
+    let mut maintenance = time::interval(MAINTENANCE_INTERVAL);
+    let mut heavy_hitter_window = time::interval(HEAVY_HITTER_WINDOW);
+    let mut unique_client_window = time::interval(UNIQUE_CLIENT_WINDOW);
+    let mut cookie_key_rotation = time::interval(COOKIE_KEY_INTERVAL);
+    loop {
+        tokio::select! {
+            msg = actor.receiver.recv() => match msg {
+                Some(msg) => actor.handle_message(msg),
+                None => break,
+            },
+            _ = maintenance.tick() => {
+                actor.remove_expired_entries();
+                actor.disable_drained_backends();
+                actor.remove_idle_traffic();
+            }
+            _ = heavy_hitter_window.tick() => actor.rotate_heavy_hitters(),
+            _ = unique_client_window.tick() => actor.rotate_unique_clients(),
+            _ = cookie_key_rotation.tick() => actor.rotate_key(Keyed::Cookies, &random_key()),
+        }
+    }
+}
+
+fn attach_program(program: &mut Xdp, iface: &str, opt: &Opt) {
     if opt.force_skb_mode {
         program
-            .attach(&opt.iface, XdpFlags::SKB_MODE)
+            .attach(iface, XdpFlags::SKB_MODE)
             .expect("Failed to attach program in SKB mode");
         println!("Attached XDP program in SKB mode.");
     } else if opt.force_hw_mode {
         program
-            .attach(&opt.iface, XdpFlags::HW_MODE)
+            .attach(iface, XdpFlags::HW_MODE)
             .expect("Failed to attach program in HW mode");
         println!("Attached XDP program in HW mode.");
     } else if opt.force_drv_mode {
         program
-            .attach(&opt.iface, XdpFlags::DRV_MODE)
+            .attach(iface, XdpFlags::DRV_MODE)
             .expect("Failed to attach program in DRV mode");
         println!("Attached XDP program in DRV mode.");
-    } else if program.attach(&opt.iface, XdpFlags::HW_MODE).is_ok() {
+    } else if program.attach(iface, XdpFlags::HW_MODE).is_ok() {
         println!("Attached XDP program in HW mode.");
-    } else if program.attach(&opt.iface, XdpFlags::DRV_MODE).is_ok() {
+    } else if program.attach(iface, XdpFlags::DRV_MODE).is_ok() {
         println!("Attached XDP program in driver mode.");
-    } else if opt.allow_skb_mode && program.attach(&opt.iface, XdpFlags::SKB_MODE).is_ok() {
+    } else if opt.allow_skb_mode && program.attach(iface, XdpFlags::SKB_MODE).is_ok() {
         println!("Attached XDP program in SKB mode.");
     } else {
         panic!(
             "Failed to bind XDP program in HW or DRV mode. You might want to try --allow-skb-mode"
         );
     }
+}
 
-    let stats = StatsMaps {
-        total_packets: PerCpuArray::try_from(bpf.take_map("TOTAL_PACKETS").unwrap()).unwrap(),
-        client_to_server_packets: PerCpuArray::try_from(
-            bpf.take_map("CLIENT_TO_SERVER_PACKETS").unwrap(),
-        )
-        .unwrap(),
-        server_to_client_packets: PerCpuArray::try_from(
-            bpf.take_map("SERVER_TO_CLIENT_PACKETS").unwrap(),
-        )
-        .unwrap(),
+fn interface_index(iface: &str) -> u32 {
+    let name = CString::new(iface).unwrap();
+    let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+    if index == 0 {
+        panic!("Unknown interface: {}", iface);
+    }
+    index
+}
+
+fn interface_mac_address(iface: &str) -> u64 {
+    let path = format!("/sys/class/net/{}/address", iface);
+    let address = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Failed to read MAC address of {}: {}", iface, err));
+
+    let mut res = 0;
+    for part in address.trim().split(':') {
+        let byte = u8::from_str_radix(part, 16).unwrap();
+        res = (res << 8) | byte as u64;
+    }
+
+    res
+}
+
+// Cookies are only checked by the router itself, so their keys are never shared and
+// can be random
+fn random_key() -> [u8; KEY_LEN] {
+    let mut key = [0; KEY_LEN];
+    fs::File::open("/dev/urandom")
+        .and_then(|mut file| file.read_exact(&mut key))
+        .expect("Failed to read random key");
+
+    key
+}
+
+// Returns the time in nanoseconds of the clock used by bpf_ktime_get_ns() an entry added
+// at `now` expires at, or zero if it never expires
+fn expires_at(now: Duration, expires_in: Option<Duration>) -> Result<u64, MapError> {
+    let Some(expires_in) = expires_in else {
+        return Ok(0);
     };
+    now.checked_add(expires_in)
+        .and_then(|expires_at| u64::try_from(expires_at.as_nanos()).ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "expiry out of range").into())
+}
 
-    let configs = ConfigMaps {
-        local_net_and_mask: Array::try_from(bpf.take_map("LOCAL_NET_AND_MASK").unwrap()).unwrap(),
-        backend_net_and_mask: Array::try_from(bpf.take_map("BACKEND_NET_AND_MASK").unwrap())
-            .unwrap(),
-        gateway_mac_address: Array::try_from(bpf.take_map("GATEWAY_MAC_ADDRESS").unwrap()).unwrap(),
+// Current time of the clock used by bpf_ktime_get_ns()
+fn monotonic_time() -> Duration {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
     };
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts) };
 
-    let mut actor = BpfActor::new(receiver, stats, configs);
-    while let Some(msg) = actor.receiver.recv().await {
-        actor.handle_message(msg);
+    Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expires_at_is_checked() {
+        let now = Duration::from_secs(100);
+
+        assert_eq!(expires_at(now, None).unwrap(), 0);
+        assert_eq!(
+            expires_at(now, Some(Duration::from_secs(20))).unwrap(),
+            120_000_000_000
+        );
+        assert!(expires_at(now, Some(Duration::MAX)).is_err());
+        assert!(expires_at(now, Some(Duration::from_secs(u64::MAX / 1_000_000_000))).is_err());
     }
 }
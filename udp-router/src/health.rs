@@ -0,0 +1,120 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    io::Read,
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+};
+
+use crate::bpf_actor::{BpfActorHandle, ProbeResult};
+use tokio::{
+    net::UdpSocket,
+    time::{self, Instant, MissedTickBehavior},
+};
+use udp_router_common::{health, HEALTH_PROBE_LEN};
+
+// Time a backend has to answer a probe
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
+// Probes the backends of all services at an interval, reporting the results to the BPF
+// actor, which marks backends up or down
+#[derive(Debug, Clone)]
+pub struct HealthChecker {
+    bpf: BpfActorHandle,
+    port: u16,
+    interval: Duration,
+}
+
+impl HealthChecker {
+    pub fn new(bpf: BpfActorHandle, port: u16, interval: Duration) -> Self {
+        HealthChecker {
+            bpf,
+            port,
+            interval,
+        }
+    }
+
+    pub async fn start(&self) {
+        let socket = UdpSocket::bind("0.0.0.0:0").await.unwrap();
+        println!(
+            "Probing backends on port {} every {:?}",
+            self.port, self.interval
+        );
+
+        // Nonces start from a random value, so answers to probes of an earlier run of
+        // the controller are not mistaken for answers to new ones
+        let mut nonce = random_nonce();
+        let mut ticker = time::interval(self.interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+
+            let backends: BTreeSet<u32> = self
+                .bpf
+                .list_services()
+                .await
+                .into_iter()
+                .flat_map(|service| service.backends.into_iter().chain(service.canaries))
+                .collect();
+            let results = self.probe(&socket, &backends, &mut nonce).await;
+            self.bpf.report_probes(results).await;
+        }
+    }
+
+    // Sends a probe to each backend and waits for the answers until the timeout
+    async fn probe(
+        &self,
+        socket: &UdpSocket,
+        backends: &BTreeSet<u32>,
+        nonce: &mut u64,
+    ) -> Vec<ProbeResult> {
+        let mut pending: HashMap<u64, (u32, Instant)> = HashMap::new();
+        for &backend in backends {
+            *nonce = nonce.wrapping_add(1);
+            let target = (Ipv4Addr::from_bits(backend), self.port);
+            if socket.send_to(&health::probe(*nonce), target).await.is_ok() {
+                pending.insert(*nonce, (backend, Instant::now()));
+            }
+        }
+
+        let mut rtts: HashMap<u32, Duration> = HashMap::new();
+        let deadline = Instant::now() + PROBE_TIMEOUT;
+        let mut buf = [0; HEALTH_PROBE_LEN + 1];
+        while !pending.is_empty() {
+            let (len, from) = match time::timeout_at(deadline, socket.recv_from(&mut buf)).await {
+                Ok(Ok(received)) => received,
+                Ok(Err(_)) => continue,
+                Err(_) => break,
+            };
+            let Some(nonce) = health::parse_reply(&buf[..len]) else {
+                continue;
+            };
+            let Some(&(backend, sent_at)) = pending.get(&nonce) else {
+                continue;
+            };
+            if from.ip() != IpAddr::V4(Ipv4Addr::from_bits(backend)) {
+                continue;
+            }
+
+            pending.remove(&nonce);
+            rtts.insert(backend, sent_at.elapsed());
+        }
+
+        backends
+            .iter()
+            .map(|&addr| ProbeResult {
+                addr,
+                rtt: rtts.get(&addr).copied(),
+            })
+            .collect()
+    }
+}
+
+fn random_nonce() -> u64 {
+    let mut nonce = [0; 8];
+    fs::File::open("/dev/urandom")
+        .and_then(|mut file| file.read_exact(&mut nonce))
+        .expect("Failed to read random nonce");
+
+    u64::from_le_bytes(nonce)
+}
@@ -1,10 +1,16 @@
+use std::time::Duration;
+
 use bpf_actor::BpfActorHandle;
 use clap::Parser;
 #[rustfmt::skip]
 use log::debug;
+use health::HealthChecker;
+use metrics::MetricsServer;
 use server::ManagementServer;
 
 mod bpf_actor;
+mod health;
+mod metrics;
 mod server;
 
 #[derive(Clone, Debug, Parser)]
@@ -12,6 +18,9 @@ struct Opt {
     /// Interface to attach XDP program to
     #[clap(short, long, default_value = "eth0")]
     iface: String,
+    /// Backend-facing interface. If set, traffic is redirected between the two interfaces
+    #[clap(long)]
+    backend_iface: Option<String>,
     /// Path to BPF program
     #[clap(long)]
     bpf_prog: String,
@@ -21,6 +30,15 @@ struct Opt {
     /// Port to bind controller to
     #[clap(long, default_value_t = 8888)]
     port: u32,
+    /// Port to serve Prometheus metrics on. Disabled if not set
+    #[clap(long)]
+    metrics_port: Option<u32>,
+    /// Seconds between health check probes of service backends. Zero disables probing
+    #[clap(long, default_value_t = 5)]
+    health_check_interval: u64,
+    /// Port backends answer health check probes on
+    #[clap(long, default_value_t = 8888)]
+    health_check_port: u16,
     /// Force binding XDP program in SKB mode
     #[clap(long, default_value_t = false)]
     force_skb_mode: bool,
@@ -50,6 +68,15 @@ async fn main() {
     }
 
     let bpf_actor = BpfActorHandle::new(&opt);
+    if let Some(port) = opt.metrics_port {
+        let metrics = MetricsServer::new(bpf_actor.clone(), &opt.bind_address, port);
+        tokio::spawn(async move { metrics.start().await });
+    }
+    if opt.health_check_interval != 0 {
+        let interval = Duration::from_secs(opt.health_check_interval);
+        let health = HealthChecker::new(bpf_actor.clone(), opt.health_check_port, interval);
+        tokio::spawn(async move { health.start().await });
+    }
     let server = ManagementServer::new(bpf_actor, &opt.bind_address, opt.port);
 
     server.start().await;
@@ -0,0 +1,305 @@
+use std::{fmt::Write, net::Ipv4Addr};
+
+use crate::bpf_actor::{BackendHealth, BackendStatistics, BpfActorHandle, RouterStatistics};
+use axum::{extract::State, http::header, response::IntoResponse, routing::get, Router};
+use tokio::net::TcpListener;
+
+// Content type of the Prometheus text exposition format
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+#[derive(Debug, Clone)]
+pub struct MetricsServer {
+    bpf: BpfActorHandle,
+    bind_address: String,
+    port: u32,
+}
+
+impl MetricsServer {
+    pub fn new(bpf: BpfActorHandle, bind_address: &str, port: u32) -> Self {
+        MetricsServer {
+            bpf,
+            bind_address: bind_address.to_string(),
+            port,
+        }
+    }
+
+    pub async fn start(&self) {
+        let addr = format!("{}:{}", self.bind_address, self.port);
+        let listener = TcpListener::bind(&addr).await.unwrap();
+        println!("Serving Prometheus metrics on {}", addr);
+
+        let app = Router::new()
+            .route("/metrics", get(metrics))
+            .with_state(self.bpf.clone());
+        axum::serve(listener, app).await.unwrap();
+    }
+}
+
+async fn metrics(State(bpf): State<BpfActorHandle>) -> impl IntoResponse {
+    let stats = bpf.get_router_stats().await;
+    let health = bpf.get_backend_health().await;
+    let body = render(&stats, &health);
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], body)
+}
+
+fn render(stats: &RouterStatistics, health: &[BackendHealth]) -> String {
+    let mut out = String::new();
+
+    for (name, help, value) in [
+        ("packets", "Packets forwarded", stats.total_packets),
+        (
+            "client_to_server_packets",
+            "Packets forwarded from clients to backends",
+            stats.client_to_server_packets,
+        ),
+        (
+            "server_to_client_packets",
+            "Packets forwarded from backends to clients",
+            stats.server_to_client_packets,
+        ),
+        (
+            "non_unicast_dropped_packets",
+            "Non-unicast frames dropped",
+            stats.non_unicast_dropped_packets,
+        ),
+        (
+            "ip_options_dropped_packets",
+            "Packets with IPv4 header options dropped",
+            stats.ip_options_dropped_packets,
+        ),
+        (
+            "udp_checksum_added_packets",
+            "Packets without UDP checksum given one",
+            stats.udp_checksum_added_packets,
+        ),
+        (
+            "ip_length_dropped_packets",
+            "Packets with invalid IPv4 total length dropped",
+            stats.ip_length_dropped_packets,
+        ),
+        (
+            "udp_length_dropped_packets",
+            "Packets with invalid UDP length dropped",
+            stats.udp_length_dropped_packets,
+        ),
+        (
+            "short_routing_header_dropped_packets",
+            "Packets with too short routing header dropped",
+            stats.short_routing_header_dropped_packets,
+        ),
+        (
+            "ttl_expired_dropped_packets",
+            "Packets with expired TTL dropped",
+            stats.ttl_expired_dropped_packets,
+        ),
+        (
+            "icmp_time_exceeded_sent_packets",
+            "ICMP Time Exceeded messages sent",
+            stats.icmp_time_exceeded_sent_packets,
+        ),
+        (
+            "fragments_dropped_packets",
+            "IPv4 fragments dropped",
+            stats.fragments_dropped_packets,
+        ),
+        (
+            "fragments_passed_packets",
+            "IPv4 fragments passed to the kernel",
+            stats.fragments_passed_packets,
+        ),
+        (
+            "first_fragments_forwarded_packets",
+            "First IPv4 fragments forwarded",
+            stats.first_fragments_forwarded_packets,
+        ),
+        (
+            "target_unspecified_dropped_packets",
+            "Packets with unspecified target address dropped",
+            stats.target_unspecified_dropped_packets,
+        ),
+        (
+            "target_loopback_dropped_packets",
+            "Packets with loopback target address dropped",
+            stats.target_loopback_dropped_packets,
+        ),
+        (
+            "target_multicast_dropped_packets",
+            "Packets with multicast target address dropped",
+            stats.target_multicast_dropped_packets,
+        ),
+        (
+            "target_broadcast_dropped_packets",
+            "Packets with broadcast target address dropped",
+            stats.target_broadcast_dropped_packets,
+        ),
+        (
+            "target_link_local_dropped_packets",
+            "Packets with link-local target address dropped",
+            stats.target_link_local_dropped_packets,
+        ),
+        (
+            "target_local_network_dropped_packets",
+            "Packets with target address in the local network dropped",
+            stats.target_local_network_dropped_packets,
+        ),
+        (
+            "target_router_dropped_packets",
+            "Packets targeting the router itself dropped",
+            stats.target_router_dropped_packets,
+        ),
+        (
+            "blocklist_dropped_packets",
+            "Packets from blocklisted clients dropped",
+            stats.blocklist_dropped_packets,
+        ),
+        (
+            "rate_limited_dropped_packets",
+            "Packets from clients over their rate limit dropped",
+            stats.rate_limited_dropped_packets,
+        ),
+        (
+            "backend_overload_dropped_packets",
+            "Packets to backends over their rate limit dropped",
+            stats.backend_overload_dropped_packets,
+        ),
+        (
+            "header_auth_dropped_packets",
+            "Packets from clients without a valid routing header tag dropped",
+            stats.header_auth_dropped_packets,
+        ),
+        (
+            "client_token_dropped_packets",
+            "Packets dropped as their client token could not be made or decoded",
+            stats.client_token_dropped_packets,
+        ),
+        (
+            "unseen_client_dropped_packets",
+            "Responses to clients not seen recently dropped",
+            stats.unseen_client_dropped_packets,
+        ),
+        (
+            "amplification_dropped_packets",
+            "Responses to clients over their amplification limit dropped",
+            stats.amplification_dropped_packets,
+        ),
+        (
+            "challenges_sent_packets",
+            "Challenges sent to clients without a valid cookie",
+            stats.challenges_sent_packets,
+        ),
+        (
+            "cookies_verified_packets",
+            "Packets from clients with a valid cookie forwarded",
+            stats.cookies_verified_packets,
+        ),
+        (
+            "cookie_dropped_packets",
+            "Packets too short to hold a cookie dropped",
+            stats.cookie_dropped_packets,
+        ),
+        (
+            "service_unavailable_dropped_packets",
+            "Packets to unknown services or services without backends dropped",
+            stats.service_unavailable_dropped_packets,
+        ),
+        (
+            "unavailable_backend_skipped_packets",
+            "Backends of services skipped as they are down",
+            stats.unavailable_backend_skipped_packets,
+        ),
+        (
+            "backend_remapped_packets",
+            "Packets sent to the new address of a backend that moved",
+            stats.backend_remapped_packets,
+        ),
+    ] {
+        write_counter(&mut out, name, help, value);
+    }
+
+    write_gauge(
+        &mut out,
+        "unique_clients",
+        "Distinct clients in the last minute, estimated",
+        stats.unique_clients,
+    );
+
+    for (name, help, value) in [
+        (
+            "backend_shed_packets",
+            "Packets to a backend over its rate limit dropped",
+            (|backend| backend.shed_packets) as fn(&BackendStatistics) -> u64,
+        ),
+        (
+            "backend_service_packets",
+            "Packets forwarded to a backend through a service",
+            |backend| backend.service_packets,
+        ),
+    ] {
+        writeln!(out, "# HELP udp_router_{}_total {}", name, help).unwrap();
+        writeln!(out, "# TYPE udp_router_{}_total counter", name).unwrap();
+        for backend in stats.backends.iter() {
+            writeln!(
+                out,
+                "udp_router_{}_total{{backend=\"{}\"}} {}",
+                name,
+                Ipv4Addr::from_bits(backend.addr),
+                value(backend)
+            )
+            .unwrap();
+        }
+    }
+
+    for (name, kind, help, value) in [
+        (
+            "backend_up",
+            "gauge",
+            "Whether a backend answers health check probes",
+            (|health| health.up as u64 as f64) as fn(&BackendHealth) -> f64,
+        ),
+        (
+            "backend_probes_succeeded_total",
+            "counter",
+            "Health check probes answered by a backend",
+            |health| health.probes_succeeded as f64,
+        ),
+        (
+            "backend_probes_failed_total",
+            "counter",
+            "Health check probes not answered by a backend in time",
+            |health| health.probes_failed as f64,
+        ),
+        (
+            "backend_probe_rtt_seconds",
+            "gauge",
+            "Round-trip time of the last health check probe answered by a backend",
+            |health| health.last_rtt.unwrap_or_default().as_secs_f64(),
+        ),
+    ] {
+        writeln!(out, "# HELP udp_router_{} {}", name, help).unwrap();
+        writeln!(out, "# TYPE udp_router_{} {}", name, kind).unwrap();
+        for health in health.iter() {
+            writeln!(
+                out,
+                "udp_router_{}{{backend=\"{}\"}} {}",
+                name,
+                Ipv4Addr::from_bits(health.addr),
+                value(health)
+            )
+            .unwrap();
+        }
+    }
+
+    out
+}
+
+fn write_gauge(out: &mut String, name: &str, help: &str, value: u64) {
+    writeln!(out, "# HELP udp_router_{} {}", name, help).unwrap();
+    writeln!(out, "# TYPE udp_router_{} gauge", name).unwrap();
+    writeln!(out, "udp_router_{} {}", name, value).unwrap();
+}
+
+fn write_counter(out: &mut String, name: &str, help: &str, value: u64) {
+    writeln!(out, "# HELP udp_router_{}_total {}", name, help).unwrap();
+    writeln!(out, "# TYPE udp_router_{}_total counter", name).unwrap();
+    writeln!(out, "udp_router_{}_total {}", name, value).unwrap();
+}
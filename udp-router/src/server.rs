@@ -1,14 +1,50 @@
-use std::net::SocketAddr;
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
 
-use crate::bpf_actor::BpfActorHandle;
+use crate::bpf_actor::{
+    self, BlocklistRule, BpfActorHandle, Keyed, Maintenance, Pool, RateLimited,
+};
 use tokio::net::TcpListener;
 use tonic::{transport::Server, Request, Response, Status};
 use udp_router_protobuf::management::router_service_server::{RouterService, RouterServiceServer};
+use udp_router_common::{
+    BACKEND_INTERFACE, CHALLENGES_DISABLED, CHALLENGES_REQUIRED, CLIENT_INTERFACE,
+    CLIENT_TOKENS_DISABLED, CLIENT_TOKENS_ENABLED, FRAGMENTS_DROP, FRAGMENTS_FORWARD_FIRST,
+    FRAGMENTS_PASS, HEADER_AUTH_DISABLED, HEADER_AUTH_REQUIRED, IP_OPTIONS_DROP,
+    IP_OPTIONS_FORWARD, MAX_AMPLIFICATION, MAX_RATE_LIMIT, MAX_SERVICES, MAX_SERVICE_BACKENDS,
+    TTL_EXPIRED_DROP, TTL_EXPIRED_ICMP, UDP_ZERO_CHECKSUM_CALCULATE, UDP_ZERO_CHECKSUM_KEEP,
+};
 use udp_router_protobuf::management::{
-    GetStatsRequest, GetStatsResponse, SetBackendNetAndMaskRequest, SetGatewayMacAddressRequest,
-    SetLocalNetAndMaskRequest,
+    AddBackendRemapsRequest, AddBlocklistEntriesRequest, BackendHealth, BackendMaintenance,
+    BackendRemap, BackendState, BackendStats, BlocklistEntry, ChallengePolicy, ClientTokenPolicy,
+    Flow, FlushFlowsRequest, FlushFlowsResponse, FragmentPolicy, GetBackendHealthRequest,
+    GetBackendHealthResponse, GetStatsRequest, GetStatsResponse, GetTopRateLimitedClientsRequest,
+    GetTopRateLimitedClientsResponse, GetTopTalkersRequest, GetTopTalkersResponse,
+    HeaderAuthPolicy, InterfaceSide, IpOptionsPolicy, ListBackendRemapsRequest,
+    ListBackendRemapsResponse, ListBackendStatesRequest, ListBackendStatesResponse,
+    ListBlocklistEntriesRequest, ListBlocklistEntriesResponse, ListFlowsRequest, ListFlowsResponse,
+    ListPrefixRateLimitsRequest, ListPrefixRateLimitsResponse, ListServicesRequest,
+    ListServicesResponse, ListTrafficCountersRequest, ListTrafficCountersResponse, PinFlowRequest,
+    PrefixRateLimit, RateLimitedClient, RemoveBackendRemapsRequest, RemoveBackendRemapsResponse,
+    RemoveBlocklistEntriesRequest, RemoveBlocklistEntriesResponse, RemovePrefixRateLimitRequest,
+    RemoveServiceBackendsResponse, RetirePreviousKeyRequest, RotateKeyRequest, Service,
+    ServiceBackendsRequest, SetBackendNetAndMaskRequest, SetBackendStateRequest,
+    SetChallengePolicyRequest, SetClientRateLimitRequest, SetClientTokenPolicyRequest,
+    SetFlowIdleTimeoutRequest, SetFragmentPolicyRequest, SetGatewayMacAddressRequest,
+    SetHeaderAuthPolicyRequest, SetIpOptionsPolicyRequest, SetLocalNetAndMaskRequest,
+    SetMaxAmplificationRequest, SetMinRoutingHeaderLenRequest, SetPrefixRateLimitRequest,
+    SetSeenClientWindowRequest, SetServiceCanaryPercentRequest, SetSourceMacAddressRequest,
+    SetTopTalkerBlockingRequest, SetTrafficClientPrefixLenRequest, SetTtlExpiredPolicyRequest,
+    SetUdpZeroChecksumPolicyRequest, TopTalker, TrafficCounter, TrafficKeyed, TtlExpiredPolicy,
+    UdpZeroChecksumPolicy, UnpinFlowRequest, UnpinFlowResponse,
 };
 
+// Longest expiry of blocklist entries and backend remaps, keeping their expiry times
+// within the nanosecond clock of the XDP program
+const MAX_EXPIRES_IN_SECS: u64 = 10 * 365 * 24 * 60 * 60;
+
 #[derive(Debug, Clone)]
 pub struct ManagementServer {
     bpf: BpfActorHandle,
@@ -69,6 +105,47 @@ impl RouterService for ManagementServer {
             total_packets: stats.total_packets,
             client_to_server_packets: stats.client_to_server_packets,
             server_to_client_packets: stats.server_to_client_packets,
+            non_unicast_dropped_packets: stats.non_unicast_dropped_packets,
+            ip_options_dropped_packets: stats.ip_options_dropped_packets,
+            udp_checksum_added_packets: stats.udp_checksum_added_packets,
+            ip_length_dropped_packets: stats.ip_length_dropped_packets,
+            udp_length_dropped_packets: stats.udp_length_dropped_packets,
+            short_routing_header_dropped_packets: stats.short_routing_header_dropped_packets,
+            ttl_expired_dropped_packets: stats.ttl_expired_dropped_packets,
+            icmp_time_exceeded_sent_packets: stats.icmp_time_exceeded_sent_packets,
+            fragments_dropped_packets: stats.fragments_dropped_packets,
+            fragments_passed_packets: stats.fragments_passed_packets,
+            first_fragments_forwarded_packets: stats.first_fragments_forwarded_packets,
+            target_unspecified_dropped_packets: stats.target_unspecified_dropped_packets,
+            target_loopback_dropped_packets: stats.target_loopback_dropped_packets,
+            target_multicast_dropped_packets: stats.target_multicast_dropped_packets,
+            target_broadcast_dropped_packets: stats.target_broadcast_dropped_packets,
+            target_link_local_dropped_packets: stats.target_link_local_dropped_packets,
+            target_local_network_dropped_packets: stats.target_local_network_dropped_packets,
+            target_router_dropped_packets: stats.target_router_dropped_packets,
+            rate_limited_dropped_packets: stats.rate_limited_dropped_packets,
+            blocklist_dropped_packets: stats.blocklist_dropped_packets,
+            backend_overload_dropped_packets: stats.backend_overload_dropped_packets,
+            backends: stats
+                .backends
+                .iter()
+                .map(|backend| BackendStats {
+                    addr: backend.addr,
+                    shed_packets: backend.shed_packets,
+                    service_packets: backend.service_packets,
+                })
+                .collect(),
+            header_auth_dropped_packets: stats.header_auth_dropped_packets,
+            client_token_dropped_packets: stats.client_token_dropped_packets,
+            unseen_client_dropped_packets: stats.unseen_client_dropped_packets,
+            amplification_dropped_packets: stats.amplification_dropped_packets,
+            challenges_sent_packets: stats.challenges_sent_packets,
+            cookies_verified_packets: stats.cookies_verified_packets,
+            cookie_dropped_packets: stats.cookie_dropped_packets,
+            service_unavailable_dropped_packets: stats.service_unavailable_dropped_packets,
+            unavailable_backend_skipped_packets: stats.unavailable_backend_skipped_packets,
+            backend_remapped_packets: stats.backend_remapped_packets,
+            unique_clients: stats.unique_clients,
         }))
     }
 
@@ -95,7 +172,885 @@ impl RouterService for ManagementServer {
         req: Request<SetGatewayMacAddressRequest>,
     ) -> Result<Response<()>, Status> {
         let req = req.into_inner();
-        self.bpf.set_gateway_mac_address(req.mac).await;
+        self.bpf
+            .set_gateway_mac_address(interface_index(req.side()), req.mac)
+            .await;
+        Ok(Response::new(()))
+    }
+
+    async fn set_source_mac_address(
+        &self,
+        req: Request<SetSourceMacAddressRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req = req.into_inner();
+        self.bpf
+            .set_source_mac_address(interface_index(req.side()), req.mac)
+            .await;
+        Ok(Response::new(()))
+    }
+
+    async fn set_ip_options_policy(
+        &self,
+        req: Request<SetIpOptionsPolicyRequest>,
+    ) -> Result<Response<()>, Status> {
+        let policy = match req.into_inner().policy() {
+            IpOptionsPolicy::IpOptionsForward => IP_OPTIONS_FORWARD,
+            IpOptionsPolicy::IpOptionsDrop => IP_OPTIONS_DROP,
+        };
+        self.bpf.set_ip_options_policy(policy).await;
+        Ok(Response::new(()))
+    }
+
+    async fn set_udp_zero_checksum_policy(
+        &self,
+        req: Request<SetUdpZeroChecksumPolicyRequest>,
+    ) -> Result<Response<()>, Status> {
+        let policy = match req.into_inner().policy() {
+            UdpZeroChecksumPolicy::UdpZeroChecksumKeep => UDP_ZERO_CHECKSUM_KEEP,
+            UdpZeroChecksumPolicy::UdpZeroChecksumCalculate => UDP_ZERO_CHECKSUM_CALCULATE,
+        };
+        self.bpf.set_udp_zero_checksum_policy(policy).await;
+        Ok(Response::new(()))
+    }
+
+    async fn set_min_routing_header_len(
+        &self,
+        req: Request<SetMinRoutingHeaderLenRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req = req.into_inner();
+        self.bpf.set_min_routing_header_len(req.len).await;
+        Ok(Response::new(()))
+    }
+
+    async fn set_ttl_expired_policy(
+        &self,
+        req: Request<SetTtlExpiredPolicyRequest>,
+    ) -> Result<Response<()>, Status> {
+        let policy = match req.into_inner().policy() {
+            TtlExpiredPolicy::TtlExpiredDrop => TTL_EXPIRED_DROP,
+            TtlExpiredPolicy::TtlExpiredIcmp => TTL_EXPIRED_ICMP,
+        };
+        self.bpf.set_ttl_expired_policy(policy).await;
+        Ok(Response::new(()))
+    }
+
+    async fn set_fragment_policy(
+        &self,
+        req: Request<SetFragmentPolicyRequest>,
+    ) -> Result<Response<()>, Status> {
+        let policy = match req.into_inner().policy() {
+            FragmentPolicy::FragmentsDrop => FRAGMENTS_DROP,
+            FragmentPolicy::FragmentsPass => FRAGMENTS_PASS,
+            FragmentPolicy::FragmentsForwardFirst => FRAGMENTS_FORWARD_FIRST,
+        };
+        self.bpf.set_fragment_policy(policy).await;
+        Ok(Response::new(()))
+    }
+
+    async fn add_blocklist_entries(
+        &self,
+        req: Request<AddBlocklistEntriesRequest>,
+    ) -> Result<Response<()>, Status> {
+        let rules = blocklist_rules(req.into_inner().entries).map_err(Status::invalid_argument)?;
+        match self.bpf.add_blocklist_rules(rules).await {
+            Ok(()) => Ok(Response::new(())),
+            Err(e) => Err(Status::resource_exhausted(format!(
+                "Failed to add blocklist entries: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn remove_blocklist_entries(
+        &self,
+        req: Request<RemoveBlocklistEntriesRequest>,
+    ) -> Result<Response<RemoveBlocklistEntriesResponse>, Status> {
+        let rules = blocklist_rules(req.into_inner().entries).map_err(Status::invalid_argument)?;
+        let removed = self.bpf.remove_blocklist_rules(rules).await;
+        Ok(Response::new(RemoveBlocklistEntriesResponse {
+            removed: removed as u32,
+        }))
+    }
+
+    async fn list_blocklist_entries(
+        &self,
+        _req: Request<ListBlocklistEntriesRequest>,
+    ) -> Result<Response<ListBlocklistEntriesResponse>, Status> {
+        let rules = self.bpf.list_blocklist_rules().await;
+        let entries = rules
+            .into_iter()
+            .map(|rule| BlocklistEntry {
+                net: rule.net,
+                prefix_len: rule.prefix_len,
+                expires_in_secs: rule.expires_in.map_or(0, |d| d.as_secs().max(1)),
+                hits: rule.hits,
+            })
+            .collect();
+        Ok(Response::new(ListBlocklistEntriesResponse { entries }))
+    }
+
+    async fn add_backend_remaps(
+        &self,
+        req: Request<AddBackendRemapsRequest>,
+    ) -> Result<Response<()>, Status> {
+        let remaps = backend_remaps(req.into_inner().remaps).map_err(Status::invalid_argument)?;
+        match self.bpf.add_backend_remaps(remaps).await {
+            Ok(()) => Ok(Response::new(())),
+            Err(e) => Err(Status::resource_exhausted(format!(
+                "Failed to add backend remaps: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn remove_backend_remaps(
+        &self,
+        req: Request<RemoveBackendRemapsRequest>,
+    ) -> Result<Response<RemoveBackendRemapsResponse>, Status> {
+        let removed = self
+            .bpf
+            .remove_backend_remaps(req.into_inner().old_backends)
+            .await;
+        Ok(Response::new(RemoveBackendRemapsResponse {
+            removed: removed as u32,
+        }))
+    }
+
+    async fn list_backend_remaps(
+        &self,
+        _req: Request<ListBackendRemapsRequest>,
+    ) -> Result<Response<ListBackendRemapsResponse>, Status> {
+        let remaps = self
+            .bpf
+            .list_backend_remaps()
+            .await
+            .into_iter()
+            .map(|remap| BackendRemap {
+                old_backend: remap.old_backend,
+                new_backend: remap.new_backend,
+                expires_in_secs: remap.expires_in.map_or(0, |d| d.as_secs().max(1)),
+                hits: remap.hits,
+            })
+            .collect();
+        Ok(Response::new(ListBackendRemapsResponse { remaps }))
+    }
+
+    async fn set_client_rate_limit(
+        &self,
+        req: Request<SetClientRateLimitRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req = req.into_inner();
+        if !valid_rate_limit(req.rate, req.burst) {
+            return Err(Status::invalid_argument("Invalid rate limit"));
+        }
+
+        self.bpf.set_client_rate_limit(req.rate, req.burst).await;
+        Ok(Response::new(()))
+    }
+
+    async fn set_prefix_rate_limit(
+        &self,
+        req: Request<SetPrefixRateLimitRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.set_rate_limit(RateLimited::Clients, req.into_inner()).await
+    }
+
+    async fn remove_prefix_rate_limit(
+        &self,
+        req: Request<RemovePrefixRateLimitRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.remove_rate_limit(RateLimited::Clients, req.into_inner()).await
+    }
+
+    async fn list_prefix_rate_limits(
+        &self,
+        _req: Request<ListPrefixRateLimitsRequest>,
+    ) -> Result<Response<ListPrefixRateLimitsResponse>, Status> {
+        self.list_rate_limits(RateLimited::Clients).await
+    }
+
+    async fn get_top_rate_limited_clients(
+        &self,
+        req: Request<GetTopRateLimitedClientsRequest>,
+    ) -> Result<Response<GetTopRateLimitedClientsResponse>, Status> {
+        let limit = req.into_inner().limit as usize;
+        let clients = self
+            .bpf
+            .get_top_rate_limited_clients(limit)
+            .await
+            .into_iter()
+            .map(|(addr, dropped_packets)| RateLimitedClient {
+                addr,
+                dropped_packets,
+            })
+            .collect();
+        Ok(Response::new(GetTopRateLimitedClientsResponse { clients }))
+    }
+
+    async fn set_backend_rate_limit(
+        &self,
+        req: Request<SetPrefixRateLimitRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.set_rate_limit(RateLimited::Backends, req.into_inner()).await
+    }
+
+    async fn remove_backend_rate_limit(
+        &self,
+        req: Request<RemovePrefixRateLimitRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.remove_rate_limit(RateLimited::Backends, req.into_inner()).await
+    }
+
+    async fn list_backend_rate_limits(
+        &self,
+        _req: Request<ListPrefixRateLimitsRequest>,
+    ) -> Result<Response<ListPrefixRateLimitsResponse>, Status> {
+        self.list_rate_limits(RateLimited::Backends).await
+    }
+
+    async fn add_service_backends(
+        &self,
+        req: Request<ServiceBackendsRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req = req.into_inner();
+        if req.service_id >= MAX_SERVICES {
+            return Err(Status::invalid_argument("Invalid service ID"));
+        }
+        if req.backends.contains(&0) {
+            return Err(Status::invalid_argument("Invalid backend address"));
+        }
+
+        if !self
+            .bpf
+            .add_service_backends(req.service_id, service_pool(req.canary), req.backends)
+            .await
+        {
+            return Err(Status::resource_exhausted(format!(
+                "Services have at most {} backends",
+                MAX_SERVICE_BACKENDS
+            )));
+        }
+        Ok(Response::new(()))
+    }
+
+    async fn remove_service_backends(
+        &self,
+        req: Request<ServiceBackendsRequest>,
+    ) -> Result<Response<RemoveServiceBackendsResponse>, Status> {
+        let req = req.into_inner();
+        let removed = self
+            .bpf
+            .remove_service_backends(req.service_id, service_pool(req.canary), req.backends)
+            .await;
+        Ok(Response::new(RemoveServiceBackendsResponse {
+            removed: removed as u32,
+        }))
+    }
+
+    async fn set_service_canary_percent(
+        &self,
+        req: Request<SetServiceCanaryPercentRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req = req.into_inner();
+        if req.canary_percent > 100 {
+            return Err(Status::invalid_argument("Invalid canary percentage"));
+        }
+
+        if !self
+            .bpf
+            .set_service_canary_percent(req.service_id, req.canary_percent)
+            .await
+        {
+            return Err(Status::not_found("No such service"));
+        }
+        Ok(Response::new(()))
+    }
+
+    async fn list_services(
+        &self,
+        _req: Request<ListServicesRequest>,
+    ) -> Result<Response<ListServicesResponse>, Status> {
+        let services = self
+            .bpf
+            .list_services()
+            .await
+            .into_iter()
+            .map(|service| Service {
+                service_id: service.id,
+                backends: service.backends,
+                canaries: service.canaries,
+                canary_percent: service.canary_percent,
+            })
+            .collect();
+        Ok(Response::new(ListServicesResponse { services }))
+    }
+
+    async fn get_backend_health(
+        &self,
+        _req: Request<GetBackendHealthRequest>,
+    ) -> Result<Response<GetBackendHealthResponse>, Status> {
+        let backends = self
+            .bpf
+            .get_backend_health()
+            .await
+            .into_iter()
+            .map(|health| BackendHealth {
+                addr: health.addr,
+                up: health.up,
+                probes_succeeded: health.probes_succeeded,
+                probes_failed: health.probes_failed,
+                last_rtt_micros: health.last_rtt.unwrap_or_default().as_micros() as u64,
+            })
+            .collect();
+        Ok(Response::new(GetBackendHealthResponse { backends }))
+    }
+
+    async fn set_backend_state(
+        &self,
+        req: Request<SetBackendStateRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req = req.into_inner();
+        let maintenance = match req.state() {
+            BackendState::BackendEnabled => None,
+            BackendState::BackendDraining => {
+                let timeout = Duration::from_secs(req.drain_timeout_secs as u64);
+                Some(Maintenance::Draining {
+                    until: Instant::now() + timeout,
+                })
+            }
+            BackendState::BackendDisabled => Some(Maintenance::Disabled),
+        };
+
+        // Backends can always be enabled so that stale states of removed backends can be cleared
+        if maintenance.is_some() {
+            let services = self.bpf.list_services().await;
+            if !services.iter().any(|service| {
+                service.backends.contains(&req.addr) || service.canaries.contains(&req.addr)
+            }) {
+                return Err(Status::not_found("No such backend"));
+            }
+        }
+
+        match self.bpf.set_backend_maintenance(req.addr, maintenance).await {
+            Ok(()) => Ok(Response::new(())),
+            Err(e) => Err(Status::resource_exhausted(format!(
+                "Failed to set backend state: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn set_flow_idle_timeout(
+        &self,
+        req: Request<SetFlowIdleTimeoutRequest>,
+    ) -> Result<Response<()>, Status> {
+        let timeout = Duration::from_secs(req.into_inner().timeout_secs as u64);
+        self.bpf.set_flow_idle_timeout(timeout).await;
+        Ok(Response::new(()))
+    }
+
+    async fn list_flows(
+        &self,
+        req: Request<ListFlowsRequest>,
+    ) -> Result<Response<ListFlowsResponse>, Status> {
+        let req = req.into_inner();
+        let backend = Some(req.backend).filter(|&backend| backend != 0);
+        let flows = self
+            .bpf
+            .list_flows(backend, req.limit as usize)
+            .await
+            .into_iter()
+            .map(|flow| Flow {
+                client_ip: flow.client_ip,
+                client_port: flow.client_port as u32,
+                service_id: flow.service_id,
+                backend: flow.backend,
+                idle_millis: flow.idle.as_millis() as u64,
+                pinned: flow.pinned,
+            })
+            .collect();
+        Ok(Response::new(ListFlowsResponse { flows }))
+    }
+
+    async fn flush_flows(
+        &self,
+        req: Request<FlushFlowsRequest>,
+    ) -> Result<Response<FlushFlowsResponse>, Status> {
+        let backend = Some(req.into_inner().backend).filter(|&backend| backend != 0);
+        let flushed = self.bpf.flush_flows(backend).await;
+        Ok(Response::new(FlushFlowsResponse {
+            flushed: flushed as u32,
+        }))
+    }
+
+    async fn pin_flow(&self, req: Request<PinFlowRequest>) -> Result<Response<()>, Status> {
+        let req = req.into_inner();
+        if req.service_id >= MAX_SERVICES {
+            return Err(Status::invalid_argument("Invalid service ID"));
+        }
+        let Ok(client_port) = u16::try_from(req.client_port) else {
+            return Err(Status::invalid_argument("Invalid client port"));
+        };
+        if req.backend == 0 {
+            return Err(Status::invalid_argument("Invalid backend address"));
+        }
+
+        let flow = bpf_actor::Flow {
+            client_ip: req.client_ip,
+            client_port,
+            service_id: req.service_id,
+            backend: req.backend,
+            idle: Duration::ZERO,
+            pinned: true,
+        };
+        match self.bpf.pin_flow(flow).await {
+            Ok(()) => Ok(Response::new(())),
+            Err(e) => Err(Status::resource_exhausted(format!(
+                "Failed to pin flow: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn unpin_flow(
+        &self,
+        req: Request<UnpinFlowRequest>,
+    ) -> Result<Response<UnpinFlowResponse>, Status> {
+        let req = req.into_inner();
+        let Ok(client_port) = u16::try_from(req.client_port) else {
+            return Err(Status::invalid_argument("Invalid client port"));
+        };
+        let unpinned = self.bpf.unpin_flow(req.client_ip, client_port).await;
+        Ok(Response::new(UnpinFlowResponse { unpinned }))
+    }
+
+    async fn set_traffic_client_prefix_len(
+        &self,
+        req: Request<SetTrafficClientPrefixLenRequest>,
+    ) -> Result<Response<()>, Status> {
+        let prefix_len = req.into_inner().prefix_len;
+        if prefix_len > 32 {
+            return Err(Status::invalid_argument("Invalid prefix length"));
+        }
+        self.bpf.set_traffic_client_prefix_len(prefix_len).await;
+        Ok(Response::new(()))
+    }
+
+    async fn list_traffic_counters(
+        &self,
+        req: Request<ListTrafficCountersRequest>,
+    ) -> Result<Response<ListTrafficCountersResponse>, Status> {
+        let req = req.into_inner();
+        let keyed = match req.keyed() {
+            TrafficKeyed::TrafficByBackend => bpf_actor::TrafficKeyed::Backends,
+            TrafficKeyed::TrafficByClientPrefix => bpf_actor::TrafficKeyed::ClientPrefixes,
+        };
+        let page = self
+            .bpf
+            .list_traffic(keyed, req.page_token, req.limit as usize)
+            .await;
+        let counters = page
+            .traffic
+            .into_iter()
+            .map(|traffic| TrafficCounter {
+                addr: traffic.addr,
+                prefix_len: traffic.prefix_len,
+                requests: traffic.requests,
+                request_bytes: traffic.request_bytes,
+                responses: traffic.responses,
+                response_bytes: traffic.response_bytes,
+                idle_millis: traffic.idle.as_millis() as u64,
+            })
+            .collect();
+        Ok(Response::new(ListTrafficCountersResponse {
+            counters,
+            next_page_token: page.next.unwrap_or(0),
+        }))
+    }
+
+    async fn get_top_talkers(
+        &self,
+        req: Request<GetTopTalkersRequest>,
+    ) -> Result<Response<GetTopTalkersResponse>, Status> {
+        let limit = match req.into_inner().limit {
+            0 => usize::MAX,
+            limit => limit as usize,
+        };
+        let talkers = self.bpf.get_top_talkers().await;
+        let top = |talkers: Vec<bpf_actor::TopTalker>| {
+            talkers
+                .into_iter()
+                .take(limit)
+                .map(|talker| TopTalker {
+                    addr: talker.addr,
+                    packets: talker.packets,
+                })
+                .collect()
+        };
+        Ok(Response::new(GetTopTalkersResponse {
+            sources: top(talkers.sources),
+            targets: top(talkers.targets),
+            window_secs: talkers.window.as_secs() as u32,
+        }))
+    }
+
+    async fn set_top_talker_blocking(
+        &self,
+        req: Request<SetTopTalkerBlockingRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req = req.into_inner();
+        let blocking = match req.packets_per_sec {
+            0 => None,
+            rate => Some(bpf_actor::TopTalkerBlocking {
+                rate,
+                duration: Duration::from_secs(req.block_secs as u64),
+            }),
+        };
+        if req.packets_per_sec > MAX_RATE_LIMIT {
+            return Err(Status::invalid_argument("Invalid rate"));
+        }
+        if blocking.is_some() && req.block_secs == 0 {
+            return Err(Status::invalid_argument("Invalid block duration"));
+        }
+        self.bpf.set_top_talker_blocking(blocking).await;
+        Ok(Response::new(()))
+    }
+
+    async fn list_backend_states(
+        &self,
+        _req: Request<ListBackendStatesRequest>,
+    ) -> Result<Response<ListBackendStatesResponse>, Status> {
+        let now = Instant::now();
+        let backends = self
+            .bpf
+            .list_backend_maintenance()
+            .await
+            .into_iter()
+            .map(|(addr, maintenance)| match maintenance {
+                Maintenance::Draining { until } => BackendMaintenance {
+                    addr,
+                    state: BackendState::BackendDraining.into(),
+                    disabled_in_secs: until.saturating_duration_since(now).as_secs(),
+                },
+                Maintenance::Disabled => BackendMaintenance {
+                    addr,
+                    state: BackendState::BackendDisabled.into(),
+                    disabled_in_secs: 0,
+                },
+            })
+            .collect();
+        Ok(Response::new(ListBackendStatesResponse { backends }))
+    }
+
+    async fn set_header_auth_policy(
+        &self,
+        req: Request<SetHeaderAuthPolicyRequest>,
+    ) -> Result<Response<()>, Status> {
+        let policy = match req.into_inner().policy() {
+            HeaderAuthPolicy::HeaderAuthDisabled => HEADER_AUTH_DISABLED,
+            HeaderAuthPolicy::HeaderAuthRequired => HEADER_AUTH_REQUIRED,
+        };
+        self.bpf.set_header_auth_policy(policy).await;
+        Ok(Response::new(()))
+    }
+
+    async fn rotate_header_key(
+        &self,
+        req: Request<RotateKeyRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.rotate_key(Keyed::HeaderTags, req).await
+    }
+
+    async fn retire_previous_header_key(
+        &self,
+        _req: Request<RetirePreviousKeyRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.bpf.retire_previous_key(Keyed::HeaderTags).await;
+        Ok(Response::new(()))
+    }
+
+    async fn set_seen_client_window(
+        &self,
+        req: Request<SetSeenClientWindowRequest>,
+    ) -> Result<Response<()>, Status> {
+        let window = Duration::from_secs(req.into_inner().window_secs as u64);
+        self.bpf.set_seen_client_window(window).await;
+        Ok(Response::new(()))
+    }
+
+    async fn set_max_amplification(
+        &self,
+        req: Request<SetMaxAmplificationRequest>,
+    ) -> Result<Response<()>, Status> {
+        let ratio = req.into_inner().ratio as u64;
+        if ratio > MAX_AMPLIFICATION {
+            return Err(Status::invalid_argument("Invalid amplification ratio"));
+        }
+
+        self.bpf.set_max_amplification(ratio).await;
         Ok(Response::new(()))
     }
+
+    async fn set_challenge_policy(
+        &self,
+        req: Request<SetChallengePolicyRequest>,
+    ) -> Result<Response<()>, Status> {
+        let policy = match req.into_inner().policy() {
+            ChallengePolicy::ChallengesDisabled => CHALLENGES_DISABLED,
+            ChallengePolicy::ChallengesRequired => CHALLENGES_REQUIRED,
+        };
+        self.bpf.set_challenge_policy(policy).await;
+        Ok(Response::new(()))
+    }
+
+    async fn set_client_token_policy(
+        &self,
+        req: Request<SetClientTokenPolicyRequest>,
+    ) -> Result<Response<()>, Status> {
+        let policy = match req.into_inner().policy() {
+            ClientTokenPolicy::ClientTokensDisabled => CLIENT_TOKENS_DISABLED,
+            ClientTokenPolicy::ClientTokensEnabled => CLIENT_TOKENS_ENABLED,
+        };
+        self.bpf.set_client_token_policy(policy).await;
+        Ok(Response::new(()))
+    }
+
+    async fn rotate_client_token_key(
+        &self,
+        req: Request<RotateKeyRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.rotate_key(Keyed::ClientTokens, req).await
+    }
+
+    async fn retire_previous_client_token_key(
+        &self,
+        _req: Request<RetirePreviousKeyRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.bpf.retire_previous_key(Keyed::ClientTokens).await;
+        Ok(Response::new(()))
+    }
+}
+
+// Prefix rate limits of clients and backends are managed the same way
+impl ManagementServer {
+    async fn set_rate_limit(
+        &self,
+        limited: RateLimited,
+        req: SetPrefixRateLimitRequest,
+    ) -> Result<Response<()>, Status> {
+        let limit = req
+            .limit
+            .ok_or_else(|| Status::invalid_argument("Missing rate limit"))?;
+        let net = prefix_net(limit.net, limit.prefix_len)
+            .ok_or_else(|| Status::invalid_argument("Invalid prefix length"))?;
+        if !valid_rate_limit(limit.rate, limit.burst) {
+            return Err(Status::invalid_argument("Invalid rate limit"));
+        }
+
+        let limit = bpf_actor::PrefixRateLimit {
+            net,
+            prefix_len: limit.prefix_len,
+            rate: limit.rate,
+            burst: limit.burst,
+        };
+        match self.bpf.set_prefix_rate_limit(limited, limit).await {
+            Ok(()) => Ok(Response::new(())),
+            Err(e) => Err(Status::resource_exhausted(format!(
+                "Failed to set prefix rate limit: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn remove_rate_limit(
+        &self,
+        limited: RateLimited,
+        req: RemovePrefixRateLimitRequest,
+    ) -> Result<Response<()>, Status> {
+        let net = prefix_net(req.net, req.prefix_len)
+            .ok_or_else(|| Status::invalid_argument("Invalid prefix length"))?;
+
+        if !self
+            .bpf
+            .remove_prefix_rate_limit(limited, net, req.prefix_len)
+            .await
+        {
+            return Err(Status::not_found("No rate limit for prefix"));
+        }
+        Ok(Response::new(()))
+    }
+
+    async fn list_rate_limits(
+        &self,
+        limited: RateLimited,
+    ) -> Result<Response<ListPrefixRateLimitsResponse>, Status> {
+        let limits = self
+            .bpf
+            .list_prefix_rate_limits(limited)
+            .await
+            .into_iter()
+            .map(|limit| PrefixRateLimit {
+                net: limit.net,
+                prefix_len: limit.prefix_len,
+                rate: limit.rate,
+                burst: limit.burst,
+            })
+            .collect();
+        Ok(Response::new(ListPrefixRateLimitsResponse { limits }))
+    }
+}
+
+// Keys of routing header tags and client tokens are rotated the same way
+impl ManagementServer {
+    async fn rotate_key(
+        &self,
+        keyed: Keyed,
+        req: Request<RotateKeyRequest>,
+    ) -> Result<Response<()>, Status> {
+        let key = req
+            .into_inner()
+            .key
+            .try_into()
+            .map_err(|_| Status::invalid_argument("Key must be 16 bytes"))?;
+        self.bpf.rotate_key(keyed, key).await;
+        Ok(Response::new(()))
+    }
+}
+
+fn interface_index(side: InterfaceSide) -> u32 {
+    match side {
+        InterfaceSide::Client => CLIENT_INTERFACE,
+        InterfaceSide::Backend => BACKEND_INTERFACE,
+    }
+}
+
+fn service_pool(canary: bool) -> Pool {
+    match canary {
+        true => Pool::Canaries,
+        false => Pool::Backends,
+    }
+}
+
+fn valid_rate_limit(rate: u64, burst: u64) -> bool {
+    rate <= MAX_RATE_LIMIT && burst <= MAX_RATE_LIMIT
+}
+
+// Returns the network address of a prefix with host bits cleared, so that each prefix
+// has a single map entry, or None if the prefix length is invalid
+fn prefix_net(net: u32, prefix_len: u32) -> Option<u32> {
+    if prefix_len > 32 {
+        return None;
+    }
+    let mask = u32::MAX.checked_shl(32 - prefix_len).unwrap_or(0);
+
+    Some(net & mask)
+}
+
+// Returns None for entries that never expire, or an error if the expiry is too far away
+fn expires_in(secs: u64) -> Result<Option<Duration>, &'static str> {
+    match secs {
+        0 => Ok(None),
+        secs if secs > MAX_EXPIRES_IN_SECS => Err("Invalid expiry"),
+        secs => Ok(Some(Duration::from_secs(secs))),
+    }
+}
+
+// Returns an error if any prefix length or expiry is invalid
+fn blocklist_rules(entries: Vec<BlocklistEntry>) -> Result<Vec<BlocklistRule>, &'static str> {
+    entries
+        .into_iter()
+        .map(|entry| {
+            Ok(BlocklistRule {
+                net: prefix_net(entry.net, entry.prefix_len).ok_or("Invalid prefix length")?,
+                prefix_len: entry.prefix_len,
+                expires_in: expires_in(entry.expires_in_secs)?,
+                hits: 0,
+            })
+        })
+        .collect()
+}
+
+// Returns an error if any new backend address or expiry is invalid
+fn backend_remaps(remaps: Vec<BackendRemap>) -> Result<Vec<bpf_actor::Remap>, &'static str> {
+    remaps
+        .into_iter()
+        .map(|remap| {
+            if remap.new_backend == 0 {
+                return Err("Invalid backend address");
+            }
+            Ok(bpf_actor::Remap {
+                old_backend: remap.old_backend,
+                new_backend: remap.new_backend,
+                expires_in: expires_in(remap.expires_in_secs)?,
+                hits: 0,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(net: u32, prefix_len: u32, expires_in_secs: u64) -> BlocklistEntry {
+        BlocklistEntry {
+            net,
+            prefix_len,
+            expires_in_secs,
+            hits: 0,
+        }
+    }
+
+    #[test]
+    fn blocklist_rules_clear_host_bits() {
+        let rules = blocklist_rules(vec![entry(0x0a0a0a0a, 8, 0), entry(0x0a0a0a0a, 32, 60)])
+            .unwrap();
+
+        assert_eq!((rules[0].net, rules[0].expires_in), (0x0a000000, None));
+        assert_eq!(
+            (rules[1].net, rules[1].expires_in),
+            (0x0a0a0a0a, Some(Duration::from_secs(60)))
+        );
+    }
+
+    #[test]
+    fn blocklist_rules_reject_invalid_entries() {
+        assert_eq!(
+            blocklist_rules(vec![entry(0x0a000000, 33, 0)]).unwrap_err(),
+            "Invalid prefix length"
+        );
+        for expires_in_secs in [MAX_EXPIRES_IN_SECS + 1, u64::MAX] {
+            assert_eq!(
+                blocklist_rules(vec![entry(0x0a000000, 8, expires_in_secs)]).unwrap_err(),
+                "Invalid expiry"
+            );
+        }
+        assert!(blocklist_rules(vec![entry(0x0a000000, 8, MAX_EXPIRES_IN_SECS)]).is_ok());
+    }
+
+    #[test]
+    fn backend_remaps_reject_invalid_remaps() {
+        let remap = |new_backend, expires_in_secs| BackendRemap {
+            old_backend: 0x0a000001,
+            new_backend,
+            expires_in_secs,
+            hits: 0,
+        };
+
+        assert_eq!(
+            backend_remaps(vec![remap(0, 0)]).unwrap_err(),
+            "Invalid backend address"
+        );
+        for expires_in_secs in [MAX_EXPIRES_IN_SECS + 1, u64::MAX] {
+            assert_eq!(
+                backend_remaps(vec![remap(0x0a000002, expires_in_secs)]).unwrap_err(),
+                "Invalid expiry"
+            );
+        }
+        let remaps = backend_remaps(vec![remap(0x0a000002, MAX_EXPIRES_IN_SECS)]).unwrap();
+        assert_eq!(
+            remaps[0].expires_in,
+            Some(Duration::from_secs(MAX_EXPIRES_IN_SECS))
+        );
+    }
 }